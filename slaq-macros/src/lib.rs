@@ -8,10 +8,11 @@ pub fn slack_api(args: TokenStream, input: TokenStream) -> TokenStream {
     let metas = syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated
         .parse(args)
         .expect("failed to parse attribute arguments");
-    let item = parse_macro_input!(input as ItemStruct);
+    let mut item = parse_macro_input!(input as ItemStruct);
 
     let mut path_lit: Option<String> = None;
     let mut response_ty: Option<syn::Ident> = None;
+    let mut prepare_fn: Option<syn::Path> = None;
 
     for meta in metas {
         if let Meta::NameValue(MetaNameValue { path, value, .. }) = meta
@@ -32,6 +33,10 @@ pub fn slack_api(args: TokenStream, input: TokenStream) -> TokenStream {
                         response_ty = Some(id.clone());
                     }
                 }
+                // Optional `fn(Self) -> Self` applied when the request body is built.
+                ("prepare", syn::Expr::Path(p)) => {
+                    prepare_fn = Some(p.path);
+                }
                 _ => {}
             }
         }
@@ -41,69 +46,29 @@ pub fn slack_api(args: TokenStream, input: TokenStream) -> TokenStream {
     let response_ty = response_ty.expect("slack_api requires response=Type");
 
     let struct_ident = item.ident.clone();
+    let (new_fn, opt_setters) = constructor_and_setters(&item);
+    strip_helper_attrs(&mut item);
 
-    // Determine required vs optional fields
-    let mut required_fields: Vec<(&syn::Ident, &Type)> = Vec::new();
-    let mut optional_fields: Vec<(&syn::Ident, &Type, Vec<Attribute>)> = Vec::new();
-    for field in &item.fields {
-        let ident = field.ident.as_ref().expect("named fields only");
-        match is_option(&field.ty) {
-            Some(inner) => {
-                let docs: Vec<Attribute> = field
-                    .attrs
-                    .iter()
-                    .filter(|a| a.path().is_ident("doc"))
-                    .cloned()
-                    .collect();
-                optional_fields.push((ident, inner, docs));
-            }
-            None => required_fields.push((ident, &field.ty)),
-        }
-    }
+    let into_body = prepare_fn.map_or_else(|| quote! { self }, |prepare| quote! { #prepare(self) });
 
-    let req_args_new = required_fields.iter().map(|(id, ty)| {
-        quote! { #id: impl ::core::convert::Into<#ty> }
-    });
-    let req_inits = required_fields.iter().map(|(id, _)| {
-        quote! { #id: #id.into() }
-    });
-    let opt_inits = optional_fields.iter().map(|(id, _, _)| {
-        quote! { #id: ::core::option::Option::None }
-    });
-    let field_inits: Vec<proc_macro2::TokenStream> = req_inits.chain(opt_inits).collect();
-
-    let opt_setters = optional_fields.iter().map(|(id, ty, docs)| {
-        if is_bool(ty) {
-            quote! {
-                #( #docs )*
-                #[must_use]
-                pub fn #id(mut self, v: bool) -> Self {
-                    self.#id = ::core::option::Option::Some(v);
-                    self
-                }
-            }
-        } else {
-            quote! {
-                #( #docs )*
-                #[must_use]
-                pub fn #id(mut self, v: impl ::core::convert::Into<#ty>) -> Self {
-                    self.#id = ::core::option::Option::Some(v.into());
-                    self
-                }
-            }
-        }
-    });
+    // Type parameters (e.g. a metadata payload) must be serializable to send.
+    let (impl_generics, ty_generics, _) = item.generics.split_for_impl();
+    let params = item.generics.type_params().map(|p| &p.ident);
+    let serialize_bounds = quote! { where #( #params: ::serde::Serialize ),* };
 
     let chat_path_doc = format!("Slack API path: {path_lit}");
     let expanded = quote! {
         #item
 
         impl #struct_ident {
-            #[must_use]
-            pub fn new( #( #req_args_new ),* ) -> Self {
-                Self { #( #field_inits ),* }
-            }
-            #( #opt_setters )*
+            #new_fn
+        }
+
+        impl #impl_generics #struct_ident #ty_generics {
+            #opt_setters
+        }
+
+        impl #impl_generics #struct_ident #ty_generics #serialize_bounds {
             /// Builds a transport-agnostic Slack request containing this payload.
             #[must_use]
             #[doc = #chat_path_doc]
@@ -112,11 +77,11 @@ pub fn slack_api(args: TokenStream, input: TokenStream) -> TokenStream {
             }
         }
 
-        impl crate::client::SlackMethod for #struct_ident {
+        impl #impl_generics crate::client::SlackMethod for #struct_ident #ty_generics #serialize_bounds {
             const PATH: &'static str = #path_lit;
             type Body = Self;
             type Response = #response_ty;
-            fn into_body(self) -> Self::Body { self }
+            fn into_body(self) -> Self::Body { #into_body }
         }
     };
 
@@ -144,9 +109,66 @@ fn is_bool(ty: &Type) -> bool {
     false
 }
 
+/// Makes a struct a typed layout block: derives serde with `type = kind` as
+/// the tag, and generates `new`, setters and a `build()` wrapping it in the
+/// `crate::blocks::Block` variant named after the struct.
 #[proc_macro_attribute]
 pub fn block(args: TokenStream, input: TokenStream) -> TokenStream {
-    // Parse attribute args like: kind = "divider"
+    let kind_lit = parse_kind("block", args);
+    tagged_builder(&kind_lit, input, &quote! { crate::blocks::Block })
+}
+
+/// Makes a struct a typed block element: derives serde with `type = kind` as
+/// the tag, and generates `new`, setters and a `build()` wrapping it in the
+/// `crate::blocks::Element` variant named after the struct.
+#[proc_macro_attribute]
+pub fn element(args: TokenStream, input: TokenStream) -> TokenStream {
+    let kind_lit = parse_kind("element", args);
+    tagged_builder(&kind_lit, input, &quote! { crate::blocks::Element })
+}
+
+/// Derives serde tagged with `type = kind` and generates `new`, setters and a
+/// `build()` wrapping the struct in the `output` enum variant of the same name.
+fn tagged_builder(
+    kind_lit: &str,
+    input: TokenStream,
+    output: &proc_macro2::TokenStream,
+) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemStruct);
+    for field in &mut item.fields {
+        if is_option(&field.ty).is_some() {
+            field
+                .attrs
+                .push(syn::parse_quote!(#[serde(skip_serializing_if = "Option::is_none")]));
+        }
+    }
+    item.attrs
+        .push(syn::parse_quote!(#[derive(::serde::Serialize, ::serde::Deserialize)]));
+    item.attrs
+        .push(syn::parse_quote!(#[serde(tag = "type", rename = #kind_lit)]));
+
+    let struct_ident = item.ident.clone();
+    let (new_fn, opt_setters) = constructor_and_setters(&item);
+    strip_helper_attrs(&mut item);
+
+    let expanded = quote! {
+        #item
+
+        impl #struct_ident {
+            #new_fn
+            #opt_setters
+            #[must_use]
+            pub fn build(self) -> #output {
+                #output::#struct_ident(self)
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Parses attribute args like: kind = "divider"
+fn parse_kind(macro_name: &str, args: TokenStream) -> String {
     let metas = syn::punctuated::Punctuated::<Meta, syn::Token![,]>::parse_terminated
         .parse(args)
         .expect("failed to parse attribute arguments");
@@ -169,15 +191,100 @@ pub fn block(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     }
 
-    let kind_lit = kind_lit.expect("block requires kind=\"...\"");
+    kind_lit.unwrap_or_else(|| panic!("{macro_name} requires kind=\"...\""))
+}
 
-    let item = parse_macro_input!(input as ItemStruct);
+/// Generates `new(required...)` and optional-field setters for a plain struct.
+///
+/// Fields typed `Option<T>` become chainable setters taking `impl Into<T>`
+/// (or `bool`); every other field is a required argument of `new`, except
+/// fields marked `#[slaq(default)]`, which start from `Default::default()`.
+#[proc_macro_attribute]
+pub fn builder(_args: TokenStream, input: TokenStream) -> TokenStream {
+    let mut item = parse_macro_input!(input as ItemStruct);
     let struct_ident = item.ident.clone();
+    let (new_fn, opt_setters) = constructor_and_setters(&item);
+    strip_helper_attrs(&mut item);
 
-    // Determine required vs optional fields
-    let mut required_fields: Vec<(&syn::Ident, &Type)> = Vec::new();
-    let mut optional_fields: Vec<(&syn::Ident, &Type, Vec<Attribute>)> = Vec::new();
-    for field in &item.fields {
+    let expanded = quote! {
+        #item
+
+        impl #struct_ident {
+            #new_fn
+            #opt_setters
+        }
+    };
+
+    expanded.into()
+}
+
+type RequiredField<'a> = (&'a syn::Ident, &'a Type);
+type OptionalField<'a> = (&'a syn::Ident, &'a Type, Vec<Attribute>, bool);
+
+/// Whether a field carries the `#[slaq(...)]` helper flag `flag`.
+fn has_flag(field: &syn::Field, flag: &str) -> bool {
+    field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("slaq"))
+        .any(|a| {
+            let mut found = false;
+            a.parse_nested_meta(|meta| {
+                found |= meta.path.is_ident(flag);
+                Ok(())
+            })
+            .expect("failed to parse #[slaq(...)]");
+            found
+        })
+}
+
+/// Whether a field is marked `#[slaq(default)]`: left out of `new` and
+/// initialized with `Default::default()`.
+fn is_defaulted(field: &syn::Field) -> bool {
+    has_flag(field, "default")
+}
+
+/// Whether a field is marked `#[slaq(retype)]`: its setter may change the
+/// struct's type parameter, e.g. to attach a typed payload.
+fn is_retyped(field: &syn::Field) -> bool {
+    has_flag(field, "retype")
+}
+
+/// Replaces every occurrence of the identifier `from` in `tokens` with `to`.
+fn replace_ident(
+    tokens: proc_macro2::TokenStream,
+    from: &syn::Ident,
+    to: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    use proc_macro2::{Group, TokenTree};
+    tokens
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) if ident == *from => TokenTree::Ident(to.clone()),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_ident(group.stream(), from, to));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced)
+            }
+            other => other,
+        })
+        .collect()
+}
+
+/// Removes the `#[slaq(...)]` helper attributes before the struct is emitted.
+fn strip_helper_attrs(item: &mut ItemStruct) {
+    for field in &mut item.fields {
+        field.attrs.retain(|a| !a.path().is_ident("slaq"));
+    }
+}
+
+/// Splits named fields into required fields and `Option<T>` fields (with their docs).
+/// Fields marked `#[slaq(default)]` are in neither.
+fn split_fields(item: &ItemStruct) -> (Vec<RequiredField<'_>>, Vec<OptionalField<'_>>) {
+    let mut required_fields: Vec<RequiredField<'_>> = Vec::new();
+    let mut optional_fields: Vec<OptionalField<'_>> = Vec::new();
+    for field in item.fields.iter().filter(|f| !is_defaulted(f)) {
         let ident = field.ident.as_ref().expect("named fields only");
         match is_option(&field.ty) {
             Some(inner) => {
@@ -187,11 +294,19 @@ pub fn block(args: TokenStream, input: TokenStream) -> TokenStream {
                     .filter(|a| a.path().is_ident("doc"))
                     .cloned()
                     .collect();
-                optional_fields.push((ident, inner, docs));
+                optional_fields.push((ident, inner, docs, is_retyped(field)));
             }
             None => required_fields.push((ident, &field.ty)),
         }
     }
+    (required_fields, optional_fields)
+}
+
+/// Builds the `new(required...)` constructor and the optional-field setters.
+fn constructor_and_setters(
+    item: &ItemStruct,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let (required_fields, optional_fields) = split_fields(item);
 
     // new(required...)
     let req_args_new = required_fields.iter().map(|(id, ty)| {
@@ -200,14 +315,21 @@ pub fn block(args: TokenStream, input: TokenStream) -> TokenStream {
     let req_inits = required_fields.iter().map(|(id, _)| {
         quote! { #id: #id.into() }
     });
-    let opt_inits = optional_fields.iter().map(|(id, _, _)| {
+    let opt_inits = optional_fields.iter().map(|(id, _, _, _)| {
         quote! { #id: ::core::option::Option::None }
     });
-    let field_inits: Vec<proc_macro2::TokenStream> = req_inits.chain(opt_inits).collect();
+    let default_inits = item.fields.iter().filter(|f| is_defaulted(f)).map(|f| {
+        let id = &f.ident;
+        quote! { #id: ::core::default::Default::default() }
+    });
+    let field_inits: Vec<proc_macro2::TokenStream> =
+        req_inits.chain(opt_inits).chain(default_inits).collect();
 
     // optional setters
-    let opt_setters = optional_fields.iter().map(|(id, ty, docs)| {
-        if is_bool(ty) {
+    let opt_setters = optional_fields.iter().map(|(id, ty, docs, retype)| {
+        if *retype {
+            retyping_setter(item, id, ty, docs)
+        } else if is_bool(ty) {
             quote! {
                 #( #docs )*
                 #[must_use]
@@ -228,54 +350,54 @@ pub fn block(args: TokenStream, input: TokenStream) -> TokenStream {
         }
     });
 
-    // build() -> crate::blocks::Block
-    let mut build_inserts_req: Vec<proc_macro2::TokenStream> = Vec::new();
-    for (id, _) in &required_fields {
-        let key = id.to_string();
-        build_inserts_req.push(quote! {
-            map.insert(
-                ::std::string::String::from(#key),
-                ::serde_json::to_value(self.#id).expect("serialize required field"),
-            );
-        });
-    }
-    let mut build_inserts_opt: Vec<proc_macro2::TokenStream> = Vec::new();
-    for (id, _, _) in &optional_fields {
-        let key = id.to_string();
-        build_inserts_opt.push(quote! {
-            if let ::core::option::Option::Some(v) = self.#id {
-                map.insert(
-                    ::std::string::String::from(#key),
-                    ::serde_json::to_value(v).expect("serialize optional field"),
-                );
-            }
-        });
-    }
+    let new_fn = quote! {
+        #[must_use]
+        pub fn new( #( #req_args_new ),* ) -> Self {
+            Self { #( #field_inits ),* }
+        }
+    };
+    (new_fn, quote! { #( #opt_setters )* })
+}
 
-    let expanded = {
-        quote! {
-            #item
+/// Builds the setter for a `#[slaq(retype)]` field typed `Option<F<P>>`, where
+/// `P` is the struct's only type parameter. The setter takes `F<T>` for any
+/// `T` and returns the struct with `P` replaced by `T`; a crate-internal
+/// `replace_<field>` does the same for an `Option<F<T>>`.
+fn retyping_setter(
+    item: &ItemStruct,
+    id: &syn::Ident,
+    ty: &Type,
+    docs: &[Attribute],
+) -> proc_macro2::TokenStream {
+    let struct_ident = &item.ident;
+    let params: Vec<&syn::Ident> = item.generics.type_params().map(|p| &p.ident).collect();
+    let [param] = params.as_slice() else {
+        panic!("#[slaq(retype)] requires exactly one type parameter");
+    };
+    let target = syn::Ident::new(if *param == "T" { "U" } else { "T" }, param.span());
+    let target_ty = replace_ident(quote! { #ty }, param, &target);
+    let replace_fn = syn::Ident::new(&format!("replace_{id}"), id.span());
+    let others = item
+        .fields
+        .iter()
+        .filter_map(|f| f.ident.as_ref())
+        .filter(|other| *other != id);
 
-            impl #struct_ident {
-                #[must_use]
-                pub fn new( #( #req_args_new ),* ) -> Self {
-                    Self { #( #field_inits ),* }
-                }
-                #( #opt_setters )*
-                #[must_use]
-                pub fn build(self) -> crate::blocks::Block {
-                    let mut map = ::serde_json::Map::new();
-                    map.insert(
-                        ::std::string::String::from("type"),
-                        ::serde_json::Value::String(::std::string::String::from(#kind_lit)),
-                    );
-                    #( #build_inserts_req )*
-                    #( #build_inserts_opt )*
-                    crate::blocks::Block(::serde_json::Value::Object(map))
-                }
-            }
+    quote! {
+        #( #docs )*
+        #[must_use]
+        pub fn #id<#target>(self, v: #target_ty) -> #struct_ident<#target> {
+            self.#replace_fn(::core::option::Option::Some(v))
         }
-    };
 
-    expanded.into()
+        /// Replaces the field, changing the struct's type parameter to match.
+        #[allow(dead_code)]
+        #[must_use]
+        pub(crate) fn #replace_fn<#target>(
+            self,
+            v: ::core::option::Option<#target_ty>,
+        ) -> #struct_ident<#target> {
+            #struct_ident { #id: v, #( #others: self.#others ),* }
+        }
+    }
 }
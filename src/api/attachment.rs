@@ -0,0 +1,115 @@
+//! Legacy secondary message attachments.
+//!
+//! Slack recommends blocks for new messages, but attachments remain the only
+//! way to get the colored sidebar and are still returned on older messages.
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json as json;
+use serde_with::skip_serializing_none;
+
+/// A secondary attachment on a message.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[slaq_macros::builder]
+pub struct Attachment {
+    /// Plain-text summary shown in clients that can't display attachments.
+    pub fallback: Option<String>,
+    /// Color of the sidebar: `good`, `warning`, `danger`, or a hex code like `#439FE0`.
+    pub color: Option<String>,
+    /// Text shown above the attachment.
+    pub pretext: Option<String>,
+    /// Small text identifying the author.
+    pub author_name: Option<String>,
+    /// URL linked from the author name.
+    pub author_link: Option<String>,
+    /// URL of a small 16x16 image shown left of the author name.
+    pub author_icon: Option<String>,
+    /// Large title text near the top of the attachment.
+    pub title: Option<String>,
+    /// URL linked from the title.
+    pub title_link: Option<String>,
+    /// Main body text of the attachment.
+    pub text: Option<String>,
+    /// Fields displayed in a table inside the attachment.
+    pub fields: Option<Vec<AttachmentField>>,
+    /// URL of an image displayed inside the attachment.
+    pub image_url: Option<String>,
+    /// URL of an image displayed as a thumbnail on the right side.
+    pub thumb_url: Option<String>,
+    /// Small text shown at the bottom of the attachment.
+    pub footer: Option<String>,
+    /// URL of a small 16x16 image shown left of the footer.
+    pub footer_icon: Option<String>,
+    /// Unix timestamp shown next to the footer.
+    #[serde(default, deserialize_with = "epoch_seconds")]
+    pub ts: Option<i64>,
+    /// Fields that should be formatted with mrkdwn, e.g. `text` or `pretext`.
+    pub mrkdwn_in: Option<Vec<String>>,
+    /// Blocks displayed inside the attachment, instead of the legacy fields.
+    pub blocks: Option<Vec<crate::blocks::Block>>,
+}
+
+/// A field displayed in an attachment's table.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[slaq_macros::builder]
+pub struct AttachmentField {
+    /// Bold heading shown above the value.
+    pub title: String,
+    /// Text value of the field.
+    pub value: String,
+    /// Whether the field is short enough to be shown side by side with others.
+    pub short: Option<bool>,
+}
+
+/// Slack returns attachment timestamps as integers, floats or numeric strings.
+fn epoch_seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    #[allow(clippy::cast_possible_truncation)]
+    let truncate = |f: f64| f.is_finite().then_some(f as i64);
+    let secs = match Option::<json::Value>::deserialize(deserializer)? {
+        None | Some(json::Value::Null) => return Ok(None),
+        Some(json::Value::Number(n)) => n.as_i64().or_else(|| n.as_f64().and_then(truncate)),
+        Some(json::Value::String(s)) => s
+            .parse::<i64>()
+            .ok()
+            .or_else(|| s.parse::<f64>().ok().and_then(truncate)),
+        Some(_) => None,
+    };
+    secs.map(Some)
+        .ok_or_else(|| D::Error::custom("expected a Unix timestamp as a number or numeric string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_serializes_set_fields_only() {
+        let attachment = Attachment::new()
+            .color("#36a64f")
+            .title("Build passed")
+            .fields(vec![AttachmentField::new("Branch", "main").short(true)])
+            .ts(1_700_000_000_i64);
+        let json = serde_json::to_string(&attachment).unwrap();
+        assert_eq!(
+            json,
+            r##"{"color":"#36a64f","title":"Build passed","fields":[{"title":"Branch","value":"main","short":true}],"ts":1700000000}"##
+        );
+    }
+
+    #[test]
+    fn attachment_ts_accepts_numbers_and_strings() {
+        let a: Attachment = serde_json::from_str(r#"{"ts":123}"#).unwrap();
+        assert_eq!(a.ts, Some(123));
+        let a: Attachment = serde_json::from_str(r#"{"ts":"1503435956.000247"}"#).unwrap();
+        assert_eq!(a.ts, Some(1_503_435_956));
+        let a: Attachment = serde_json::from_str(r"{}").unwrap();
+        assert_eq!(a.ts, None);
+        let a: Attachment = serde_json::from_str(r#"{"ts":null}"#).unwrap();
+        assert_eq!(a.ts, None);
+        for bad in [r#"{"ts":"yesterday"}"#, r#"{"ts":""}"#, r#"{"ts":true}"#] {
+            assert!(serde_json::from_str::<Attachment>(bad).is_err(), "{bad}");
+        }
+    }
+}
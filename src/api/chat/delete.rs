@@ -15,9 +15,9 @@ use serde_with::skip_serializing_none;
 )]
 pub struct Delete {
     /// Channel containing the message to be deleted.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Timestamp of the message to be deleted.
-    pub ts: String,
+    pub ts: crate::id::Ts,
     /// (Legacy) Pass true to delete the message as the authed user with `chat:write:user` scope. Bot users in this context are considered authed users. See legacy `as_user` parameter below[0].
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.delete#legacy_as_user>
@@ -28,7 +28,7 @@ pub struct Delete {
 /// Response for `chat.delete`.
 pub struct DeleteResponse {
     /// Channel ID where the message was deleted.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Timestamp of the deleted message.
-    pub ts: String,
+    pub ts: crate::id::Ts,
 }
@@ -15,7 +15,7 @@ use serde_with::skip_serializing_none;
 )]
 pub struct DeleteScheduledMessage {
     /// The channel the `scheduled_message` is posting to
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// `scheduled_message_id` returned from call to chat.scheduleMessage
     pub scheduled_message_id: String,
     /// Pass true to delete the message as the authed user with `chat:write:user` scope. Bot users in this context are considered authed users. If unused or false, the message will be deleted with `chat:write:bot` scope.
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Retrieve a permalink URL for a specific extant message.
+///
+/// Bot token: none
+/// User token: none
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Default)]
+#[slaq_macros::slack_api(
+    path="/chat.getPermalink",
+    chat_method=get_permalink,
+    response=GetPermalinkResponse,
+    call_alias="GetPermalinkCall"
+)]
+pub struct GetPermalink {
+    /// The ID of the conversation or channel containing the message.
+    pub channel: crate::id::ChannelId,
+    /// A message's `ts` value, uniquely identifying it within a channel.
+    pub message_ts: crate::id::Ts,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+/// Response for `chat.getPermalink`.
+pub struct GetPermalinkResponse {
+    /// Channel ID containing the message.
+    pub channel: crate::id::ChannelId,
+    /// Permalink URL of the message.
+    pub permalink: String,
+}
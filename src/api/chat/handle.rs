@@ -0,0 +1,153 @@
+use crate::api::chat::delete::{Delete, DeleteResponse};
+use crate::api::chat::get_permalink::GetPermalink;
+use crate::api::chat::payload::{ContentError, MessagePayload};
+use crate::api::chat::post_message::{PostMessage, PostMessageResponse};
+use crate::api::chat::update::{Update, UpdateResponse};
+use crate::api::reactions::add::Add;
+use crate::client::Execute;
+use crate::id::{ChannelId, Ts};
+use serde::Serialize;
+use thiserror::Error;
+
+/// An error from sending message content, e.g. by [`PostMessage::post`].
+///
+/// Content is validated before anything is sent.
+#[derive(Debug, Error)]
+pub enum SendError<E> {
+    #[error(transparent)]
+    Content(#[from] ContentError),
+    #[error("Slack request failed: {0}")]
+    Client(E),
+}
+
+/// A posted message bound to the client that posted it.
+///
+/// Follow-up calls (updates, thread replies, deletes, permalinks and
+/// reactions) are built against the same `channel` and `ts` and executed
+/// with the same client.
+#[derive(Debug)]
+pub struct MessageHandle<'a, C> {
+    client: &'a C,
+    /// Channel ID containing the message.
+    pub channel: ChannelId,
+    /// Timestamp of the message.
+    pub ts: Ts,
+    /// Timestamp of the thread parent, if the message is a thread reply.
+    pub thread_ts: Option<Ts>,
+}
+
+impl<C> Clone for MessageHandle<'_, C> {
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client,
+            channel: self.channel.clone(),
+            ts: self.ts,
+            thread_ts: self.thread_ts,
+        }
+    }
+}
+
+impl<'a, C: Execute> MessageHandle<'a, C> {
+    /// Creates a handle for an existing message.
+    #[must_use]
+    pub fn new(client: &'a C, channel: impl Into<ChannelId>, ts: impl Into<Ts>) -> Self {
+        Self {
+            client,
+            channel: channel.into(),
+            ts: ts.into(),
+            thread_ts: None,
+        }
+    }
+
+    /// Marks the message as a reply in the thread started by `thread_ts`.
+    #[must_use]
+    pub fn thread_ts(mut self, thread_ts: impl Into<Ts>) -> Self {
+        self.thread_ts = Some(thread_ts.into());
+        self
+    }
+
+    /// Updates the message via `chat.update`.
+    ///
+    /// The closure receives an `Update` already targeting this message.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` if the update's content is invalid, before
+    /// sending anything, or the transport error if the request fails.
+    pub fn update<M: Serialize>(
+        &self,
+        build: impl FnOnce(Update) -> Update<M>,
+    ) -> Result<UpdateResponse, SendError<C::Error>> {
+        let request = build(Update::new(self.channel.clone(), self.ts));
+        request.validate()?;
+        self.client.execute(request).map_err(SendError::Client)
+    }
+
+    /// Posts a reply in the message's thread via `chat.postMessage`.
+    ///
+    /// Replies to a reply are posted to the original thread parent.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` if the reply's content is invalid, before
+    /// sending anything, or the transport error if the request fails.
+    pub fn reply<M: Serialize>(
+        &self,
+        build: impl FnOnce(PostMessage) -> PostMessage<M>,
+    ) -> Result<MessageHandle<'a, C>, SendError<C::Error>> {
+        let thread_ts = self.thread_ts.unwrap_or(self.ts);
+        build(PostMessage::new(self.channel.clone()))
+            .thread_ts(thread_ts)
+            .post(self.client)
+    }
+
+    /// Deletes the message via `chat.delete`.
+    ///
+    /// # Errors
+    /// Returns the transport error if the request fails.
+    pub fn delete(self) -> Result<DeleteResponse, C::Error> {
+        self.client.execute(Delete::new(self.channel, self.ts))
+    }
+
+    /// Retrieves the message's permalink via `chat.getPermalink`.
+    ///
+    /// # Errors
+    /// Returns the transport error if the request fails.
+    pub fn permalink(&self) -> Result<String, C::Error> {
+        self.client
+            .execute(GetPermalink::new(self.channel.clone(), self.ts))
+            .map(|resp| resp.permalink)
+    }
+
+    /// Adds a reaction to the message via `reactions.add`.
+    ///
+    /// # Errors
+    /// Returns the transport error if the request fails.
+    pub fn react(&self, name: impl Into<String>) -> Result<(), C::Error> {
+        self.client
+            .execute(Add::new(self.channel.clone(), name, self.ts))
+            .map(|_| ())
+    }
+}
+
+impl<M: Serialize> PostMessage<M> {
+    /// Posts the message and returns a handle for follow-up calls.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` if the content is invalid, before sending
+    /// anything, or the transport error if the request fails.
+    pub fn post<C: Execute>(self, client: &C) -> Result<MessageHandle<'_, C>, SendError<C::Error>> {
+        self.validate()?;
+        let thread_ts = self.thread_ts;
+        let resp = client.execute(self).map_err(SendError::Client)?;
+        let mut handle = resp.handle(client);
+        handle.thread_ts = thread_ts;
+        Ok(handle)
+    }
+}
+
+impl PostMessageResponse {
+    /// Returns a handle for follow-up calls on the posted message.
+    #[must_use]
+    pub fn handle<'a, C: Execute>(&self, client: &'a C) -> MessageHandle<'a, C> {
+        MessageHandle::new(client, self.channel.clone(), self.ts)
+    }
+}
@@ -15,7 +15,7 @@ use serde_with::skip_serializing_none;
 )]
 pub struct MeMessage {
     /// Channel to send message to. Can be a public channel, private group or IM channel. Can be an encoded ID, or a name.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Text of the message to send.
     pub text: String,
 }
@@ -24,7 +24,7 @@ pub struct MeMessage {
 /// Response for `chat.meMessage`.
 pub struct MeMessageResponse {
     /// Channel ID where the message was posted.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Timestamp of the posted message.
-    pub ts: String,
+    pub ts: crate::id::Ts,
 }
@@ -1,9 +1,13 @@
 pub mod delete;
 pub mod delete_scheduled_message;
+pub mod get_permalink;
+pub mod handle;
 pub mod me_message;
+pub mod payload;
 pub mod post_ephemeral;
 pub mod post_message;
 pub mod schedule_message;
 pub mod scheduled_messages_list;
+pub mod split;
 pub mod unfurl;
 pub mod update;
@@ -0,0 +1,384 @@
+//! Content shared by the message-like `chat` methods.
+//!
+//! `PostMessage`, `PostEphemeral`, `ScheduleMessage`, `Update` and `MeMessage`
+//! all carry the same content fields. `MessagePayload` exposes them uniformly,
+//! and `MessageContent` lets content be rendered once and delivered in any mode.
+//!
+//! Slack only accepts some combinations of `text`, `blocks` and
+//! `markdown_text`. `MessageBody` models the valid choices, and
+//! `MessagePayload::validate` catches invalid ones before sending, along with
+//! blocks that break Slack's limits. Legacy `attachments` may accompany any
+//! body, or stand in for one.
+
+use crate::api::attachment::Attachment;
+use crate::api::chat::me_message::MeMessage;
+use crate::api::chat::post_ephemeral::PostEphemeral;
+use crate::api::chat::post_message::PostMessage;
+use crate::api::chat::schedule_message::ScheduleMessage;
+use crate::api::chat::update::Update;
+use crate::blocks::{self, Block, BlockErrors};
+use crate::id::{ChannelId, Ts, UserId};
+use crate::mrkdwn::Mrkdwn;
+use crate::schedule::{PostAt, PostAtError};
+use thiserror::Error;
+
+/// The body of a message: one of Slack's mutually exclusive content forms.
+#[derive(Debug, Clone)]
+pub enum MessageBody {
+    /// Plain `text`.
+    Text(String),
+    /// `blocks`, with `text` used as the notification fallback.
+    Blocks {
+        blocks: Vec<Block>,
+        fallback: Option<String>,
+    },
+    /// `markdown_text`. Limit 12,000 characters.
+    Markdown(String),
+}
+
+/// Invalid combinations of message content fields.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ContentError {
+    #[error("message has no text, blocks, markdown_text or attachments")]
+    Empty,
+    #[error("markdown_text cannot be combined with text or blocks")]
+    MarkdownWithOtherContent,
+    #[error("invalid blocks: {0}")]
+    InvalidBlocks(#[from] BlockErrors),
+    #[error("{0} cannot be sent with this method")]
+    Unsupported(&'static str),
+}
+
+/// Checks that the given content fields form a valid `MessageBody` and that
+/// any blocks are within Slack's limits.
+fn validate_fields(
+    text: Option<&str>,
+    blocks: Option<&[Block]>,
+    markdown_text: Option<&str>,
+    attachments: Option<&[Attachment]>,
+) -> Result<(), ContentError> {
+    match (text, blocks, markdown_text) {
+        (None, None, None) if attachments.is_none() => Err(ContentError::Empty),
+        (Some(_), _, Some(_)) | (_, Some(_), Some(_)) => {
+            Err(ContentError::MarkdownWithOtherContent)
+        }
+        (_, Some(blocks), _) => Ok(blocks::validate(blocks)?),
+        _ => Ok(()),
+    }
+}
+
+/// Message content independent of how it is delivered.
+#[slaq_macros::builder]
+#[derive(Debug, Clone, Default)]
+pub struct MessageContent {
+    /// Message text, or the notification fallback when `blocks` are set.
+    pub text: Option<String>,
+    /// Structured blocks.
+    pub blocks: Option<Vec<Block>>,
+    /// Message text formatted in markdown. Not to be combined with `blocks` or `text`.
+    pub markdown_text: Option<String>,
+    /// Legacy secondary attachments.
+    pub attachments: Option<Vec<Attachment>>,
+    /// Find and link user groups.
+    pub link_names: Option<bool>,
+    /// Change how messages are treated.
+    pub parse: Option<String>,
+}
+
+impl MessageContent {
+    /// Checks that the content fields form a valid `MessageBody`.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` for empty content, conflicting fields or
+    /// invalid blocks.
+    pub fn validate(&self) -> Result<(), ContentError> {
+        validate_fields(
+            self.text.as_deref(),
+            self.blocks.as_deref(),
+            self.markdown_text.as_deref(),
+            self.attachments.as_deref(),
+        )
+    }
+
+    /// Sets `text` from mrkdwn. Plain strings are escaped, so they can't
+    /// inject mentions or links; see [`crate::mrkdwn`].
+    #[must_use]
+    pub fn mrkdwn_text(mut self, text: impl Into<Mrkdwn>) -> Self {
+        self.text = Some(text.into().into_string());
+        self
+    }
+
+    /// Replaces `text`, `blocks` and `markdown_text` with the given body.
+    #[must_use]
+    pub fn body(mut self, body: MessageBody) -> Self {
+        (self.text, self.blocks, self.markdown_text) = match body {
+            MessageBody::Text(text) => (Some(text), None, None),
+            MessageBody::Blocks { blocks, fallback } => (fallback, Some(blocks), None),
+            MessageBody::Markdown(markdown) => (None, None, Some(markdown)),
+        };
+        self
+    }
+
+    /// Delivers the content as a `chat.postMessage` to `channel`.
+    #[must_use]
+    pub fn post_message(self, channel: impl Into<ChannelId>) -> PostMessage {
+        PostMessage::new(channel).replace_content(self)
+    }
+
+    /// Delivers the content as a `chat.postEphemeral` to `user` in `channel`.
+    #[must_use]
+    pub fn post_ephemeral(
+        self,
+        channel: impl Into<ChannelId>,
+        user: impl Into<UserId>,
+    ) -> PostEphemeral {
+        PostEphemeral::new(channel, user).replace_content(self)
+    }
+
+    /// Delivers the content as a `chat.scheduleMessage` to `channel` at `post_at`.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if `post_at` is not in the future or is more
+    /// than 120 days ahead.
+    pub fn schedule_message(
+        self,
+        channel: impl Into<ChannelId>,
+        post_at: impl Into<PostAt>,
+    ) -> Result<ScheduleMessage, PostAtError> {
+        let request = ScheduleMessage::new(channel, post_at).replace_content(self);
+        request.validate_post_at()?;
+        Ok(request)
+    }
+
+    /// Delivers the content as a `chat.update` of the message `ts` in `channel`.
+    #[must_use]
+    pub fn update(self, channel: impl Into<ChannelId>, ts: impl Into<Ts>) -> Update {
+        Update::new(channel, ts).replace_content(self)
+    }
+}
+
+impl From<MessageBody> for MessageContent {
+    fn from(body: MessageBody) -> Self {
+        MessageContent::new().body(body)
+    }
+}
+
+/// A `chat` method payload carrying message content.
+pub trait MessagePayload {
+    /// Checks that the content fields form a valid `MessageBody` before sending.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` for empty content, conflicting fields or
+    /// invalid blocks.
+    fn validate(&self) -> Result<(), ContentError>;
+    /// Returns a copy of the content carried by this payload.
+    fn content(&self) -> MessageContent;
+    /// Replaces the content carried by this payload.
+    ///
+    /// # Errors
+    /// Returns `ContentError::Unsupported`, leaving the payload unchanged, if
+    /// the method can't carry some of the content.
+    fn set_content(&mut self, content: MessageContent) -> Result<(), ContentError>;
+    /// Builder-style variant of [`MessagePayload::set_content`].
+    ///
+    /// # Errors
+    /// Returns `ContentError::Unsupported` if the method can't carry some of
+    /// the content.
+    fn with_content(mut self, content: MessageContent) -> Result<Self, ContentError>
+    where
+        Self: Sized,
+    {
+        self.set_content(content)?;
+        Ok(self)
+    }
+    /// Replaces `text`, `blocks` and `markdown_text` with the given body,
+    /// keeping the other content fields.
+    ///
+    /// # Errors
+    /// Returns `ContentError::Unsupported` if the method can't carry the body.
+    fn with_body(self, body: MessageBody) -> Result<Self, ContentError>
+    where
+        Self: Sized,
+    {
+        let content = self.content().body(body);
+        self.with_content(content)
+    }
+}
+
+macro_rules! impl_message_payload {
+    ($($ty:ident$(<$param:ident>)?),* $(,)?) => {$(
+        impl$(<$param>)? MessagePayload for $ty$(<$param>)? {
+            fn validate(&self) -> Result<(), ContentError> {
+                validate_fields(
+                    self.text.as_deref(),
+                    self.blocks.as_deref(),
+                    self.markdown_text.as_deref(),
+                    self.attachments.as_deref(),
+                )
+            }
+
+            fn content(&self) -> MessageContent {
+                MessageContent {
+                    text: self.text.clone(),
+                    blocks: self.blocks.clone(),
+                    markdown_text: self.markdown_text.clone(),
+                    attachments: self.attachments.clone(),
+                    link_names: self.link_names,
+                    parse: self.parse.clone(),
+                }
+            }
+
+            fn set_content(&mut self, content: MessageContent) -> Result<(), ContentError> {
+                self.text = content.text;
+                self.blocks = content.blocks;
+                self.markdown_text = content.markdown_text;
+                self.attachments = content.attachments;
+                self.link_names = content.link_names;
+                self.parse = content.parse;
+                Ok(())
+            }
+        }
+
+        impl$(<$param>)? $ty$(<$param>)? {
+            /// Sets `text` from mrkdwn. Plain strings are escaped, so they
+            /// can't inject mentions or links; see [`crate::mrkdwn`].
+            #[must_use]
+            pub fn mrkdwn_text(mut self, text: impl Into<Mrkdwn>) -> Self {
+                self.text = Some(text.into().into_string());
+                self
+            }
+
+            /// Replaces the content; these methods carry every content field.
+            pub(crate) fn replace_content(mut self, content: MessageContent) -> Self {
+                let _ = self.set_content(content);
+                self
+            }
+        }
+    )*};
+}
+
+impl_message_payload!(PostMessage<M>, PostEphemeral, ScheduleMessage<M>, Update<M>);
+
+macro_rules! impl_text_from_blocks {
+    ($($ty:ident),* $(,)?) => {$(
+        impl<M> $ty<M> {
+            /// Fills `text` from `blocks` if `text_from_blocks` is set and `text` is unset.
+            pub(crate) fn fill_text_from_blocks(mut self) -> Self {
+                if self.text_from_blocks == Some(true)
+                    && self.text.is_none()
+                    && let Some(blocks) = &self.blocks
+                {
+                    let text = blocks::fallback_text(blocks);
+                    self.text = (!text.is_empty()).then_some(text);
+                }
+                self
+            }
+        }
+    )*};
+}
+
+impl_text_from_blocks!(PostMessage, ScheduleMessage, Update);
+
+/// `chat.meMessage` only carries `text`; other content fields are rejected.
+impl MessagePayload for MeMessage {
+    fn validate(&self) -> Result<(), ContentError> {
+        if self.text.is_empty() {
+            return Err(ContentError::Empty);
+        }
+        Ok(())
+    }
+
+    fn content(&self) -> MessageContent {
+        MessageContent::new().text(self.text.clone())
+    }
+
+    fn set_content(&mut self, content: MessageContent) -> Result<(), ContentError> {
+        let unsupported = [
+            ("blocks", content.blocks.is_some()),
+            ("markdown_text", content.markdown_text.is_some()),
+            ("attachments", content.attachments.is_some()),
+            ("link_names", content.link_names.is_some()),
+            ("parse", content.parse.is_some()),
+        ];
+        if let Some((field, _)) = unsupported.into_iter().find(|(_, set)| *set) {
+            return Err(ContentError::Unsupported(field));
+        }
+        self.text = content.text.ok_or(ContentError::Empty)?;
+        Ok(())
+    }
+}
+
+impl<M> PostMessage<M> {
+    /// Converts this message into a `chat.scheduleMessage` posting at `post_at`.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if `post_at` is not in the future or is more
+    /// than 120 days ahead.
+    pub fn into_schedule_message(
+        self,
+        post_at: impl Into<PostAt>,
+    ) -> Result<ScheduleMessage<M>, PostAtError> {
+        let content = self.content();
+        let request = ScheduleMessage {
+            as_user: self.as_user,
+            reply_broadcast: self.reply_broadcast,
+            text_from_blocks: self.text_from_blocks,
+            thread_ts: self.thread_ts,
+            unfurl_links: self.unfurl_links,
+            unfurl_media: self.unfurl_media,
+            ..ScheduleMessage::new(self.channel, post_at)
+        }
+        .replace_content(content)
+        .replace_metadata(self.metadata);
+        request.validate_post_at()?;
+        Ok(request)
+    }
+
+    /// Converts this message into a `chat.postEphemeral` visible only to `user`.
+    ///
+    /// `text_from_blocks` is applied before converting, since ephemeral
+    /// messages have no such option.
+    ///
+    /// # Errors
+    /// Returns `ContentError::Unsupported` if `metadata`, `reply_broadcast`,
+    /// `unfurl_links` or `unfurl_media` is set, as `chat.postEphemeral` can't
+    /// carry them.
+    pub fn into_post_ephemeral(
+        self,
+        user: impl Into<UserId>,
+    ) -> Result<PostEphemeral, ContentError> {
+        let unsupported = [
+            ("metadata", self.metadata.is_some()),
+            ("reply_broadcast", self.reply_broadcast.is_some()),
+            ("unfurl_links", self.unfurl_links.is_some()),
+            ("unfurl_media", self.unfurl_media.is_some()),
+        ];
+        if let Some((field, _)) = unsupported.into_iter().find(|(_, set)| *set) {
+            return Err(ContentError::Unsupported(field));
+        }
+        let this = self.fill_text_from_blocks();
+        let content = this.content();
+        Ok(PostEphemeral {
+            as_user: this.as_user,
+            icon_emoji: this.icon_emoji,
+            icon_url: this.icon_url,
+            thread_ts: this.thread_ts,
+            username: this.username,
+            ..PostEphemeral::new(this.channel, user)
+        }
+        .replace_content(content))
+    }
+
+    /// Converts this message into a `chat.update` of the message `ts`.
+    #[must_use]
+    pub fn into_update(self, ts: impl Into<Ts>) -> Update<M> {
+        let content = self.content();
+        Update {
+            as_user: self.as_user,
+            text_from_blocks: self.text_from_blocks,
+            ..Update::new(self.channel, ts)
+        }
+        .replace_content(content)
+        .replace_metadata(self.metadata)
+    }
+}
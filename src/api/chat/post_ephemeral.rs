@@ -15,20 +15,14 @@ use serde_with::skip_serializing_none;
 )]
 pub struct PostEphemeral {
     /// Channel, private group, or IM channel to send message to. Can be an encoded ID, or a name.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// `id` of the user who will receive the ephemeral message. The user should be in the channel specified by the `channel` argument.
-    pub user: String,
+    pub user: crate::id::UserId,
     /// (Legacy) Pass true to post the message as the authed user. Defaults to true if the chat:write:bot scope is not included. Otherwise, defaults to false.
     pub as_user: Option<bool>,
-    /*
     /// A JSON-based array of structured attachments, presented as a URL-encoded string.
-    ///
-    /// Note: Should be provided as a Vec<Attachment>
-    // pub attachments: Option<Vec<Attachment>>,
-     */
+    pub attachments: Option<Vec<crate::api::attachment::Attachment>>,
     /// A JSON-based array of structured blocks.
-    ///
-    /// Note: Uses a simplified internal `Block` representation.
     pub blocks: Option<Vec<crate::blocks::Block>>,
     /// Emoji to use as the icon for this message. Overrides `icon_url`.
     pub icon_emoji: Option<String>,
@@ -47,7 +41,7 @@ pub struct PostEphemeral {
     /// [0]: <https://docs.slack.dev/reference/methods/chat.postephemeral#text_usage>
     pub text: Option<String>,
     /// Provide another message's `ts` value to post this message in a thread. Avoid using a reply's `ts` value; use its parent's value instead. Ephemeral messages in threads are only shown if there is already an active thread.
-    pub thread_ts: Option<String>,
+    pub thread_ts: Option<crate::id::Ts>,
     /// Set your bot's user name.
     pub username: Option<String>,
 }
@@ -56,5 +50,5 @@ pub struct PostEphemeral {
 /// Response for `chat.postEphemeral`.
 pub struct PostEphemeralResponse {
     /// Timestamp of the ephemeral message that was posted.
-    pub message_ts: String,
+    pub message_ts: crate::id::Ts,
 }
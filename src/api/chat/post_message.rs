@@ -11,26 +11,21 @@ use serde_with::skip_serializing_none;
     path="/chat.postMessage",
     chat_method=post_message,
     response=PostMessageResponse,
+    prepare=Self::fill_text_from_blocks,
     call_alias="PostMessageCall"
 )]
-pub struct PostMessage {
+pub struct PostMessage<M = serde_json::Value> {
     /// An encoded ID or channel name that represents a channel, private group, or IM channel to send the message to. See below[0] for more details.
     ///
     /// [0] <https://docs.slack.dev/reference/methods/chat.postmessage#channels>
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// (Legacy) Pass true to post the message as the authed user instead of as a bot. Defaults to false. Can only be used by classic apps. See legacy `as_user` parameter below[0].
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.postmessage#legacy_as_user>
     pub as_user: Option<bool>,
-    /*
     /// A JSON-based array of structured attachments, presented as a URL-encoded string.
-    ///
-    /// Note: Should be provided as a Vec<Attachment>
-    // pub attachments: Option<Vec<Attachment>>,
-     */
+    pub attachments: Option<Vec<crate::api::attachment::Attachment>>,
     /// A JSON-based array of structured blocks, presented as a URL-encoded string.
-    ///
-    /// Note: Uses a simplified internal `Block` representation.
     pub blocks: Option<Vec<crate::blocks::Block>>,
     /// This field represents the timestamp of the draft's last update at the time this API is called. If the current message is a draft, this field can be provided to ensure synchronization with the server.
     pub current_draft_last_updated_ts: Option<String>,
@@ -44,12 +39,9 @@ pub struct PostMessage {
     pub link_names: Option<bool>,
     /// Accepts message text formatted in markdown. This argument should not be used in conjunction with `blocks` or `text`. Limit this field to 12,000 characters.
     pub markdown_text: Option<String>,
-    /*
-    /// JSON object with event_type and event_payload fields, presented as a URL-encoded string. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
-    ///
-    /// Note: Should be provided as Metadata
-    // pub metadata: Option<Metadata>
-     */
+    /// JSON object with `event_type` and `event_payload` fields. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
+    #[slaq(retype)]
+    pub metadata: Option<crate::api::metadata::Metadata<M>>,
     /// Disable Slack markup parsing by setting to `false`. Enabled by default.
     pub mrkdwn: Option<bool>,
     /// Change how messages are treated. See below[0].
@@ -62,8 +54,12 @@ pub struct PostMessage {
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.postmessage#text_usage>
     pub text: Option<String>,
+    /// Fill `text` from `blocks` with `blocks::fallback_text` when the request
+    /// is built, if `text` is unset. Not sent to Slack.
+    #[serde(skip)]
+    pub text_from_blocks: Option<bool>,
     /// Provide another message's `ts` value to make this message a reply. Avoid using a reply's `ts` value; use its parent instead.
-    pub thread_ts: Option<String>,
+    pub thread_ts: Option<crate::id::Ts>,
     /// Pass true to enable unfurling of primarily text-based content.
     pub unfurl_links: Option<bool>,
     /// Pass false to disable unfurling of media content.
@@ -74,4 +70,11 @@ pub struct PostMessage {
 
 #[derive(Debug, Clone, Deserialize)]
 /// Response for `chat.postMessage`.
-pub struct PostMessageResponse {}
+pub struct PostMessageResponse {
+    /// Channel ID where the message was posted.
+    pub channel: crate::id::ChannelId,
+    /// Timestamp of the posted message.
+    pub ts: crate::id::Ts,
+    /// The message as posted.
+    pub message: crate::api::message::Message,
+}
@@ -11,24 +11,19 @@ use serde_with::skip_serializing_none;
     path="/chat.scheduleMessage",
     chat_method=schedule_message,
     response=ScheduleMessageResponse,
+    prepare=Self::fill_text_from_blocks,
     call_alias="ScheduleMessageCall"
 )]
-pub struct ScheduleMessage {
+pub struct ScheduleMessage<M = serde_json::Value> {
     /// Channel, private group, or DM channel to send message to. Can be an encoded ID, or a name. See below[0] for more details.
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.schedulemessage#channels>
-    pub channel: String,
-    /// Unix timestamp representing the future time the message should post to Slack.
-    pub post_at: i64,
-    /*
+    pub channel: crate::id::ChannelId,
+    /// The future time the message should post to Slack, at most 120 days ahead.
+    pub post_at: crate::schedule::PostAt,
     /// A JSON-based array of structured attachments, presented as a URL-encoded string.
-    ///
-    /// Note: Should be provided as a Vec<Attachment>
-    // pub attachments: Option<Vec<Attachment>>,
-     */
+    pub attachments: Option<Vec<crate::api::attachment::Attachment>>,
     /// A JSON-based array of structured blocks, presented as a URL-encoded string.
-    ///
-    /// Note: Uses a simplified internal `Block` representation.
     pub blocks: Option<Vec<crate::blocks::Block>>,
     /// Find and link user groups. No longer supports linking individual users; use syntax shown in Mentioning Users[0] instead.
     ///
@@ -36,12 +31,9 @@ pub struct ScheduleMessage {
     pub link_names: Option<bool>,
     /// Accepts message text formatted in markdown. This argument should not be used in conjunction with `blocks` or `text`. Limit this field to 12,000 characters.
     pub markdown_text: Option<String>,
-    /*
-    /// JSON object with event_type and event_payload fields, presented as a URL-encoded string. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
-    ///
-    /// Note: Should be provided as Metadata
-    // pub metadata: Option<Metadata>,
-     */
+    /// JSON object with `event_type` and `event_payload` fields. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
+    #[slaq(retype)]
+    pub metadata: Option<crate::api::metadata::Metadata<M>>,
     /// Set to `true` to post the message as the authed user, instead of as a bot. Defaults to false. Cannot be used by new Slack apps. See chat.postMessage[0].
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.postmessage#authorship>
@@ -56,8 +48,12 @@ pub struct ScheduleMessage {
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.schedulemessage#text_usage>
     pub text: Option<String>,
+    /// Fill `text` from `blocks` with `blocks::fallback_text` when the request
+    /// is built, if `text` is unset. Not sent to Slack.
+    #[serde(skip)]
+    pub text_from_blocks: Option<bool>,
     /// Provide another message's `ts` value to make this message a reply. Avoid using a reply's `ts` value; use its parent instead.
-    pub thread_ts: Option<String>,
+    pub thread_ts: Option<crate::id::Ts>,
     /// Pass true to enable unfurling of primarily text-based content.
     pub unfurl_links: Option<bool>,
     /// Pass false to disable unfurling of media content.
@@ -68,9 +64,19 @@ pub struct ScheduleMessage {
 /// Response for `chat.scheduleMessage`.
 pub struct ScheduleMessageResponse {
     /// Channel ID where the message is scheduled.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Identifier for the scheduled message.
     pub scheduled_message_id: String,
     /// When the message is scheduled to post.
-    pub post_at: String,
+    pub post_at: crate::schedule::PostAt,
+}
+
+impl<M> ScheduleMessage<M> {
+    /// Checks that `post_at` is in the future and at most 120 days ahead.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if `post_at` is outside Slack's window.
+    pub fn validate_post_at(&self) -> Result<(), crate::schedule::PostAtError> {
+        self.post_at.validate()
+    }
 }
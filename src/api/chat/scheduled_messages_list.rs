@@ -15,7 +15,7 @@ use serde_with::skip_serializing_none;
 )]
 pub struct ScheduledMessagesList {
     /// The channel of the scheduled messages
-    pub channel: Option<String>,
+    pub channel: Option<crate::id::ChannelId>,
     /// For pagination purposes, this is the `cursor` value returned from a previous call to `chat.scheduledmessages.list` indicating where you want to start this call from.
     pub cursor: Option<String>,
     /// A Unix timestamp of the latest value in the time range
@@ -25,7 +25,7 @@ pub struct ScheduledMessagesList {
     /// A Unix timestamp of the oldest value in the time range
     pub oldest: Option<String>,
     /// encoded team id to list channels in, required if org token is used
-    pub team_id: Option<String>,
+    pub team_id: Option<crate::id::TeamId>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -37,11 +37,46 @@ pub struct ScheduledMessagesListResponse {
     pub response_metadata: Option<serde_json::Value>,
 }
 
+impl ScheduledMessagesListResponse {
+    /// The cursor for the next page, if there is one.
+    #[must_use]
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.response_metadata
+            .as_ref()?
+            .get("next_cursor")?
+            .as_str()
+            .filter(|cursor| !cursor.is_empty())
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
+/// A message waiting to be posted.
 pub struct ScheduledMessage {
-    pub id: i64,
-    pub channel_id: String,
-    pub post_at: i64,
+    /// Identifier of the scheduled message, e.g. `Q1298393284`.
+    pub id: String,
+    /// Channel the message will post to.
+    pub channel_id: crate::id::ChannelId,
+    /// When the message will post.
+    pub post_at: crate::schedule::PostAt,
+    /// When the message was scheduled, as a Unix timestamp.
     pub date_created: i64,
+    /// The message text.
+    #[serde(default)]
     pub text: String,
+    /// The thread the message will reply in, when Slack returns it.
+    pub thread_ts: Option<crate::id::Ts>,
+    /// The message blocks, when Slack returns them.
+    pub blocks: Option<Vec<crate::blocks::Block>>,
+}
+
+impl ScheduledMessage {
+    /// The message's content as far as Slack returns it: `text` and any
+    /// `blocks`. Attachments and other fields are not listed by Slack.
+    #[must_use]
+    pub fn content(&self) -> crate::api::chat::payload::MessageContent {
+        let mut content = crate::api::chat::payload::MessageContent::new();
+        content.text = (!self.text.is_empty()).then(|| self.text.clone());
+        content.blocks.clone_from(&self.blocks);
+        content
+    }
 }
@@ -0,0 +1,507 @@
+//! Splitting oversized messages into a thread of messages.
+//!
+//! Slack rejects `markdown_text` over 12,000 characters, section text over
+//! 3000 characters and more than 50 blocks, and truncates long `text`.
+//! `PostMessage::split` turns one oversized message into a `MessageSeries`:
+//! the first message plus continuations that are posted as replies in its
+//! thread. Text is split at line boundaries where possible, and code fences
+//! cut by a split are closed and reopened so each part renders on its own.
+
+use crate::api::chat::handle::{MessageHandle, SendError};
+use crate::api::chat::payload::MessagePayload;
+use crate::api::chat::post_message::PostMessage;
+use crate::blocks::{Block, Markdown, MrkdwnText, PlainText, Section, Text};
+use crate::client::Execute;
+use serde::Serialize;
+
+const FENCE: &str = "```";
+
+/// Size limits applied when splitting, in characters and blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitLimits {
+    /// Maximum length of `text`. Slack truncates `text` after 40,000
+    /// characters, but recommends keeping it under 4000.
+    pub text: usize,
+    /// Maximum length of `markdown_text`, and of all `markdown` blocks in one
+    /// message combined.
+    pub markdown_text: usize,
+    /// Maximum length of a section block's text.
+    pub section_text: usize,
+    /// Maximum number of blocks in one message.
+    pub blocks: usize,
+}
+
+impl Default for SplitLimits {
+    fn default() -> Self {
+        Self {
+            text: 4000,
+            markdown_text: 12_000,
+            section_text: 3000,
+            blocks: 50,
+        }
+    }
+}
+
+/// A message split into parts, posted as a thread.
+#[derive(Debug, Clone)]
+pub struct MessageSeries<M = serde_json::Value> {
+    /// The first message, carrying the original's attachments and thread settings.
+    pub first: PostMessage<M>,
+    /// The remaining parts, posted as replies to `first` in order.
+    pub continuations: Vec<PostMessage<M>>,
+}
+
+impl<M> MessageSeries<M> {
+    /// Returns `true` if the message didn't need splitting.
+    #[must_use]
+    pub fn is_single(&self) -> bool {
+        self.continuations.is_empty()
+    }
+
+    /// All messages in posting order. Continuations have no `thread_ts` until
+    /// posted, unless the original was itself a reply.
+    pub fn messages(&self) -> impl Iterator<Item = &PostMessage<M>> {
+        std::iter::once(&self.first).chain(&self.continuations)
+    }
+}
+
+impl<M: Serialize> MessageSeries<M> {
+    /// Posts the first message, then each continuation as a reply in its
+    /// thread, or in the original's thread if it was a reply.
+    ///
+    /// Returns a handle per posted message.
+    ///
+    /// # Errors
+    /// Returns a `ContentError` if any part's content is invalid, before
+    /// sending anything, or the transport error of the first failed request;
+    /// messages posted before it are not deleted.
+    pub fn post<C: Execute>(
+        self,
+        client: &C,
+    ) -> Result<Vec<MessageHandle<'_, C>>, SendError<C::Error>> {
+        for message in self.messages() {
+            message.validate()?;
+        }
+        let first = self.first.post(client)?;
+        let thread_ts = first.thread_ts.unwrap_or(first.ts);
+        let mut handles = Vec::with_capacity(1 + self.continuations.len());
+        handles.push(first);
+        for message in self.continuations {
+            handles.push(message.thread_ts(thread_ts).post(client)?);
+        }
+        Ok(handles)
+    }
+}
+
+impl<M: Clone> PostMessage<M> {
+    /// Splits the message into parts within Slack's default limits.
+    ///
+    /// See [`PostMessage::split_with`].
+    #[must_use]
+    pub fn split(self) -> MessageSeries<M> {
+        self.split_with(&SplitLimits::default())
+    }
+
+    /// Splits the message into parts within `limits`.
+    ///
+    /// Whichever of `markdown_text`, `blocks` or `text` carries the content is
+    /// split; with `blocks`, `text` stays on the first message as its
+    /// notification fallback, shortened at a line break if it is over
+    /// `limits.text`. Continuations keep the channel and display
+    /// options such as `username` and `unfurl_links`, but not attachments,
+    /// metadata or `reply_broadcast`.
+    #[must_use]
+    pub fn split_with(mut self, limits: &SplitLimits) -> MessageSeries<M> {
+        let parts: Vec<Part> = if let Some(markdown) = &self.markdown_text {
+            split_text(markdown, limits.markdown_text)
+                .into_iter()
+                .map(Part::Markdown)
+                .collect()
+        } else if let Some(blocks) = self.blocks.take() {
+            if let Some(text) = &mut self.text
+                && text.chars().count() > limits.text
+            {
+                *text = split_text(text, limits.text).swap_remove(0);
+            }
+            split_blocks(blocks, limits)
+                .into_iter()
+                .map(Part::Blocks)
+                .collect()
+        } else if let Some(text) = &self.text {
+            split_text(text, limits.text)
+                .into_iter()
+                .map(Part::Text)
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut template = self.clone();
+        template.text = None;
+        template.blocks = None;
+        template.markdown_text = None;
+        template.attachments = None;
+        template.metadata = None;
+        template.reply_broadcast = None;
+
+        let mut parts = parts.into_iter();
+        match parts.next() {
+            Some(Part::Blocks(blocks)) => self.blocks = Some(blocks),
+            Some(Part::Markdown(markdown)) => self.markdown_text = Some(markdown),
+            Some(Part::Text(text)) => self.text = Some(text),
+            None => {}
+        }
+        MessageSeries {
+            first: self,
+            continuations: parts.map(|part| part.apply(template.clone())).collect(),
+        }
+    }
+}
+
+/// One message's share of the split content.
+enum Part {
+    Text(String),
+    Markdown(String),
+    Blocks(Vec<Block>),
+}
+
+impl Part {
+    fn apply<M>(self, mut message: PostMessage<M>) -> PostMessage<M> {
+        match self {
+            Part::Text(text) => message.text = Some(text),
+            Part::Markdown(markdown) => message.markdown_text = Some(markdown),
+            Part::Blocks(blocks) => message.blocks = Some(blocks),
+        }
+        message
+    }
+}
+
+/// Splits text into chunks of at most `limit` characters.
+///
+/// Chunks end at line breaks where possible, then at whitespace. A ```` ``` ````
+/// code block cut by a split is closed at the end of one chunk and reopened
+/// at the start of the next.
+#[must_use]
+pub fn split_text(text: &str, limit: usize) -> Vec<String> {
+    let close = format!("\n{FENCE}");
+    let reopen = format!("{FENCE}\n");
+    // Leave room to close and reopen a fence in every chunk.
+    let room = limit.saturating_sub(close.len() + reopen.len()).max(1);
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut len = 0;
+    // Whether `current` ends inside a code block.
+    let mut in_fence = false;
+    // Where the line opening the current code block starts in `current`,
+    // until a line follows it.
+    let mut opener_at = None;
+    for line in text.split_inclusive('\n') {
+        let toggles = line.matches(FENCE).count() % 2 == 1;
+        let opens = toggles && !in_fence;
+        let pieces = hard_split(line, room);
+        let last = pieces.len() - 1;
+        for (i, piece) in pieces.into_iter().enumerate() {
+            let piece_len = piece.chars().count();
+            let fenced_after = if i == last {
+                in_fence != toggles
+            } else {
+                in_fence || toggles
+            };
+            // A chunk ending with this piece drops its trailing newline, and
+            // closes a code block the piece leaves open.
+            let closing = if fenced_after { close.len() } else { 0 };
+            let fits = |len: usize| {
+                len + piece_len + closing <= limit + usize::from(piece.ends_with('\n'))
+            };
+            if len > 0 && !fits(len) {
+                if in_fence && let Some(pos) = opener_at.filter(|&pos| pos > 0) {
+                    // Move the opening line to the next chunk rather than
+                    // end this one with an empty code block.
+                    let opener = current.split_off(pos);
+                    let chunk = finish_chunk(std::mem::replace(&mut current, opener));
+                    if !chunk.is_empty() {
+                        chunks.push(chunk);
+                    }
+                    len = current.chars().count();
+                    opener_at = Some(0);
+                }
+                if !fits(len) {
+                    let mut chunk = finish_chunk(std::mem::take(&mut current));
+                    if in_fence {
+                        chunk.push_str(&close);
+                        current.push_str(&reopen);
+                    }
+                    chunks.push(chunk);
+                    len = current.len();
+                    opener_at = None;
+                }
+            }
+            if i == 0 {
+                opener_at = opens.then_some(current.len());
+            }
+            current.push_str(piece);
+            len += piece_len;
+            in_fence = fenced_after;
+        }
+    }
+    let current = finish_chunk(current);
+    if !current.is_empty() || chunks.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Drops the line break a chunk ends with.
+fn finish_chunk(mut chunk: String) -> String {
+    if chunk.ends_with('\n') {
+        chunk.pop();
+    }
+    chunk
+}
+
+/// Splits a line longer than `limit` characters, preferring whitespace in the
+/// second half of each piece.
+fn hard_split(line: &str, limit: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut rest = line;
+    while rest.chars().count() > limit {
+        let end = rest
+            .char_indices()
+            .nth(limit)
+            .map_or(rest.len(), |(i, _)| i);
+        let window = &rest[..end];
+        let cut = window
+            .rfind(char::is_whitespace)
+            .filter(|&i| window[..i].chars().count() >= limit / 2)
+            .map_or(end, |i| {
+                i + window[i..].chars().next().map_or(1, char::len_utf8)
+            });
+        pieces.push(&rest[..cut]);
+        rest = &rest[cut..];
+    }
+    pieces.push(rest);
+    pieces
+}
+
+/// Splits blocks into groups that each fit in one message.
+///
+/// Section text and `markdown` blocks over their limits are first split into
+/// several blocks, with the `block_id` and other fields kept on the first.
+/// Groups hold at most `limits.blocks` blocks and `limits.markdown_text`
+/// characters of `markdown` blocks.
+#[must_use]
+pub fn split_blocks(blocks: Vec<Block>, limits: &SplitLimits) -> Vec<Vec<Block>> {
+    let mut groups: Vec<Vec<Block>> = Vec::new();
+    let mut current = Vec::new();
+    let mut markdown_len = 0;
+    for block in blocks
+        .into_iter()
+        .flat_map(|block| split_block(block, limits))
+    {
+        let block_markdown = match &block {
+            Block::Markdown(markdown) => markdown.text.chars().count(),
+            _ => 0,
+        };
+        if !current.is_empty()
+            && (current.len() >= limits.blocks
+                || markdown_len + block_markdown > limits.markdown_text)
+        {
+            groups.push(std::mem::take(&mut current));
+            markdown_len = 0;
+        }
+        markdown_len += block_markdown;
+        current.push(block);
+    }
+    if !current.is_empty() || groups.is_empty() {
+        groups.push(current);
+    }
+    groups
+}
+
+/// Splits one block whose text is over its limit.
+fn split_block(block: Block, limits: &SplitLimits) -> Vec<Block> {
+    match block {
+        Block::Section(mut section) => {
+            let Some(text) = section.text.take() else {
+                return vec![Block::Section(section)];
+            };
+            let source = match &text {
+                Text::Mrkdwn(mrkdwn) => &mrkdwn.text,
+                Text::PlainText(plain) => &plain.text,
+            };
+            if source.chars().count() <= limits.section_text {
+                section.text = Some(text);
+                return vec![Block::Section(section)];
+            }
+            // Each part keeps the text object's `emoji` or `verbatim` flag.
+            let make = |chunk: String| match &text {
+                Text::Mrkdwn(mrkdwn) => Text::Mrkdwn(MrkdwnText {
+                    text: chunk,
+                    ..mrkdwn.clone()
+                }),
+                Text::PlainText(plain) => Text::PlainText(PlainText {
+                    text: chunk,
+                    ..plain.clone()
+                }),
+            };
+            let mut chunks = split_text(source, limits.section_text).into_iter();
+            section.text = chunks.next().map(make);
+            let expand = section.expand;
+            std::iter::once(Block::Section(section))
+                .chain(chunks.map(|chunk| {
+                    Section {
+                        text: Some(make(chunk)),
+                        expand,
+                        ..Section::new()
+                    }
+                    .build()
+                }))
+                .collect()
+        }
+        Block::Markdown(markdown) if markdown.text.chars().count() > limits.markdown_text => {
+            let mut chunks = split_text(&markdown.text, limits.markdown_text).into_iter();
+            let first = Markdown {
+                text: chunks.next().unwrap_or_default(),
+                ..markdown
+            };
+            std::iter::once(first.build())
+                .chain(chunks.map(|chunk| Markdown::new(chunk).build()))
+                .collect()
+        }
+        block => vec![block],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::Divider;
+
+    #[test]
+    fn text_splits_at_lines_within_limit() {
+        let text = "aaaa\nbbbb\ncccc\ndddd";
+        assert_eq!(split_text(text, 10), ["aaaa\nbbbb", "cccc\ndddd"]);
+        assert_eq!(split_text("short", 10), ["short"]);
+        assert_eq!(split_text("", 10), [""]);
+    }
+
+    #[test]
+    fn long_lines_split_at_whitespace() {
+        let chunks = split_text("one two three four five six seven eight nine", 20);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 20), "{chunks:?}");
+        assert_eq!(
+            chunks.concat(),
+            "one two three four five six seven eight nine"
+        );
+        assert_eq!(chunks[0], "one two three four ");
+    }
+
+    #[test]
+    fn code_fences_are_closed_and_reopened() {
+        let text = "Log:\n```\nline 1\nline 2\nline 3\nline 4\n```\ndone";
+        let chunks = split_text(text, 26);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 26), "{chunks:?}");
+        for chunk in &chunks {
+            assert_eq!(chunk.matches(FENCE).count() % 2, 0, "{chunk:?}");
+        }
+        assert_eq!(chunks[0], "Log:\n```\nline 1\nline 2\n```");
+        assert_eq!(chunks[1], "```\nline 3\nline 4\n```\ndone");
+    }
+
+    #[test]
+    fn fence_opening_lines_move_to_the_next_chunk() {
+        let text = "aaaaaaaaaaaaaaaaaaaaaaaaa\n```\nbbbbbbbbbbbbbbbb\n```";
+        let chunks = split_text(text, 30);
+        assert_eq!(
+            chunks,
+            ["aaaaaaaaaaaaaaaaaaaaaaaaa", "```\nbbbbbbbbbbbbbbbb\n```"]
+        );
+
+        let text = "aaaa\n```\nbbbbbbbbbbbbbbbbbbbb\n```";
+        let chunks = split_text(text, 30);
+        assert_eq!(chunks, ["aaaa", "```\nbbbbbbbbbbbbbbbbbbbb\n```"]);
+
+        for limit in 20..60 {
+            let text = "intro line\n```\nfirst line\nsecond line\n```\nafter\n```\nx\n```";
+            let chunks = split_text(text, limit);
+            for chunk in &chunks {
+                assert!(chunk.chars().count() <= limit, "{limit}: {chunks:?}");
+                assert_eq!(chunk.matches(FENCE).count() % 2, 0, "{limit}: {chunks:?}");
+                assert!(!chunk.contains("```\n```"), "{limit}: {chunks:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn blocks_group_by_count_and_long_sections_split() {
+        let limits = SplitLimits {
+            section_text: 10,
+            blocks: 3,
+            ..SplitLimits::default()
+        };
+        let blocks = vec![
+            Section::new()
+                .text(Text::mrkdwn("aaaa\nbbbb\ncccc"))
+                .block_id("s")
+                .build(),
+            Divider::new().build(),
+            Divider::new().build(),
+        ];
+        let groups = split_blocks(blocks, &limits);
+        let kinds: Vec<Vec<&str>> = groups
+            .iter()
+            .map(|g| g.iter().map(Block::kind).collect())
+            .collect();
+        assert_eq!(
+            kinds,
+            [vec!["section", "section", "divider"], vec!["divider"]]
+        );
+        let Block::Section(second) = &groups[0][1] else {
+            panic!("expected section");
+        };
+        assert!(second.block_id.is_none());
+    }
+
+    #[test]
+    fn continuation_sections_keep_expand_and_text_flags() {
+        let limits = SplitLimits {
+            section_text: 10,
+            ..SplitLimits::default()
+        };
+        let section = |text: Text| Section::new().text(text).expand(true).build();
+        let blocks = vec![
+            section(Text::PlainText(
+                PlainText::new("aaaa\nbbbb\ncccc").emoji(true),
+            )),
+            section(Text::Mrkdwn(
+                MrkdwnText::new("aaaa\nbbbb\ncccc").verbatim(true),
+            )),
+        ];
+        let split = split_blocks(blocks, &limits).concat();
+        assert_eq!(split.len(), 4);
+        for block in &split {
+            let Block::Section(section) = block else {
+                panic!("expected section");
+            };
+            assert_eq!(section.expand, Some(true));
+            match section.text.as_ref().unwrap() {
+                Text::PlainText(plain) => assert_eq!(plain.emoji, Some(true)),
+                Text::Mrkdwn(mrkdwn) => assert_eq!(mrkdwn.verbatim, Some(true)),
+            }
+        }
+    }
+
+    #[test]
+    fn markdown_blocks_share_the_markdown_limit() {
+        let limits = SplitLimits {
+            markdown_text: 10,
+            ..SplitLimits::default()
+        };
+        let blocks = vec![
+            Markdown::new("123456").build(),
+            Markdown::new("123456").build(),
+        ];
+        assert_eq!(split_blocks(blocks, &limits).len(), 2);
+    }
+}
@@ -1,12 +1,18 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
+use crate::api::attachment::Attachment;
+use crate::blocks::Block;
+use crate::id::{ChannelId, Ts};
+
 /// Provide custom unfurl behavior for user-posted URLs
 ///
 /// Bot token: chat:write
 /// User token: chat:write
 #[skip_serializing_none]
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, Serialize)]
 #[slaq_macros::slack_api(
     path="/chat.unfurl",
     chat_method=unfurl,
@@ -14,12 +20,12 @@ use serde_with::skip_serializing_none;
     call_alias="UnfurlCall"
 )]
 pub struct Unfurl {
-    /// Channel ID of the message. Both `channel` and `ts` must be provided together, or `unfurl_id` and `source` must be provided together.
-    pub channel: String,
-    /// Timestamp of the message to add unfurl behavior to.
-    pub ts: String,
-    /// URL-encoded JSON map with keys set to URLs featured in the the message, pointing to their unfurl blocks or message attachments.
-    pub unfurls: String,
+    /// The message to add unfurl behavior to. Either `channel` and `ts` must be provided together, or `unfurl_id` and `source` must be provided together.
+    #[serde(flatten)]
+    pub target: UnfurlTarget,
+    /// Map with keys set to URLs featured in the the message, pointing to their unfurl blocks or message attachments. Starts empty; add entries with [`Unfurl::unfurl`].
+    #[slaq(default)]
+    pub unfurls: BTreeMap<String, UnfurlContent>,
     /// Provide a simply-formatted string to send as an ephemeral message to the user as invitation to authenticate further and enable full unfurling behavior. Provides two buttons, `Not now` or `Never ask me again`.
     pub user_auth_message: Option<String>,
     /// Set to `true` or `1` to indicate the user must install your Slack app to trigger unfurls for this domain
@@ -27,11 +33,93 @@ pub struct Unfurl {
     /// Send users to this custom URL where they will complete authentication in your app to fully trigger unfurling. Value should be properly URL-encoded.
     pub user_auth_url: Option<String>,
     /// Provide a JSON based array of structured blocks presented as URL-encoded string to send as an ephemeral message to the user as invitation to authenticate further and enable full unfurling behavior
-    ///
-    /// Note: Uses a simplified internal `Block` representation.
     pub user_auth_blocks: Option<Vec<crate::blocks::Block>>,
 }
 
+impl Unfurl {
+    /// Adds the unfurl content for a single URL.
+    #[must_use]
+    pub fn unfurl(mut self, url: impl Into<String>, content: impl Into<UnfurlContent>) -> Self {
+        self.unfurls.insert(url.into(), content.into());
+        self
+    }
+}
+
+/// Addresses the message whose URLs are unfurled.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum UnfurlTarget {
+    /// A posted message, by channel ID and timestamp.
+    Message {
+        /// Channel ID of the message.
+        channel: ChannelId,
+        /// Timestamp of the message to add unfurl behavior to.
+        ts: Ts,
+    },
+    /// A link from a `link_shared` event, by its unfurl ID and source.
+    UnfurlId {
+        /// The ID of the link to unfurl.
+        unfurl_id: String,
+        /// The source of the link to unfurl.
+        source: UnfurlSource,
+    },
+}
+
+impl UnfurlTarget {
+    /// Targets a posted message by channel ID and timestamp.
+    #[must_use]
+    pub fn message(channel: impl Into<ChannelId>, ts: impl Into<Ts>) -> Self {
+        Self::Message {
+            channel: channel.into(),
+            ts: ts.into(),
+        }
+    }
+
+    /// Targets a link by the `unfurl_id` and `source` from a `link_shared` event.
+    #[must_use]
+    pub fn unfurl_id(unfurl_id: impl Into<String>, source: UnfurlSource) -> Self {
+        Self::UnfurlId {
+            unfurl_id: unfurl_id.into(),
+            source,
+        }
+    }
+}
+
+/// Where a link to unfurl was shared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnfurlSource {
+    /// The link is in the message composer and hasn't been posted yet.
+    Composer,
+    /// The link is in a posted message.
+    ConversationsHistory,
+}
+
+/// The unfurl shown for a single URL.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum UnfurlContent {
+    /// Unfurl rendered from blocks.
+    Blocks {
+        /// Blocks shown in the unfurl.
+        blocks: Vec<Block>,
+    },
+    /// Unfurl rendered from a legacy attachment.
+    Attachment(Box<Attachment>),
+}
+
+impl From<Vec<Block>> for UnfurlContent {
+    fn from(blocks: Vec<Block>) -> Self {
+        Self::Blocks { blocks }
+    }
+}
+
+impl From<Attachment> for UnfurlContent {
+    fn from(attachment: Attachment) -> Self {
+        Self::Attachment(Box::new(attachment))
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 /// Response for `chat.unfurl`.
 pub struct UnfurlResponse {}
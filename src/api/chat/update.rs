@@ -11,33 +11,25 @@ use serde_with::skip_serializing_none;
     path="/chat.update",
     chat_method=update,
     response=UpdateResponse,
+    prepare=Self::fill_text_from_blocks,
     call_alias="UpdateCall"
 )]
-pub struct Update {
+pub struct Update<M = serde_json::Value> {
     /// Channel containing the message to be updated. For direct messages, ensure that this value is a DM ID (starts with `D`) instead of a User ID (starts with either `U` or `W`).
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Timestamp of the message to be updated.
-    pub ts: String,
+    pub ts: crate::id::Ts,
     /// Pass true to update the message as the authed user. Bot users in this context are considered authed users.
     pub as_user: Option<bool>,
-    /*
     /// A JSON-based array of structured attachments, presented as a URL-encoded string.
-    ///
-    /// Note: Should be provided as a Vec<Attachment>
-    // pub attachments: Option<Vec<Attachment>>,
-     */
+    pub attachments: Option<Vec<crate::api::attachment::Attachment>>,
     /// A JSON-based array of structured blocks, presented as a URL-encoded string.
-    ///
-    /// Note: Uses a simplified internal `Block` representation.
     pub blocks: Option<Vec<crate::blocks::Block>>,
     /// Accepts message text formatted in markdown. This argument should not be used in conjunction with `blocks` or `text`. Limit this field to 12,000 characters.
     pub markdown_text: Option<String>,
-    /*
-    /// JSON object with event_type and event_payload fields, presented as a URL-encoded string. If you don't include this field, the message's previous `metadata` will be retained. To remove previous `metadata`, include an empty object for this field. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
-    ///
-    /// Note: Should be provided as Metadata
-    // pub metadata: Option<Metadata>,
-     */
+    /// JSON object with `event_type` and `event_payload` fields. If you don't include this field, the message's previous `metadata` will be retained. To remove previous `metadata`, pass `Metadata::empty()`, which is sent as an empty object. Metadata you post to Slack is accessible to any app or user who is a member of that workspace.
+    #[slaq(retype)]
+    pub metadata: Option<crate::api::metadata::Metadata<M>>,
     /// Find and link channel names and usernames. Defaults to `none`. If you do not specify a value for this field, the original value set for the message will be overwritten with the default, `none`.
     pub link_names: Option<bool>,
     /// Change how messages are treated. Defaults to `client`, unlike `chat.postMessage`. Accepts either `none` or `full`. If you do not specify a value for this field, the original value set for the message will be overwritten with the default, `client`.
@@ -46,13 +38,21 @@ pub struct Update {
     ///
     /// [0]: <https://docs.slack.dev/reference/methods/chat.update#text_usage>
     pub text: Option<String>,
+    /// Fill `text` from `blocks` with `blocks::fallback_text` when the request
+    /// is built, if `text` is unset. Not sent to Slack.
+    #[serde(skip)]
+    pub text_from_blocks: Option<bool>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 /// Response for `chat.update`.
 pub struct UpdateResponse {
     /// Channel ID where the message was updated.
-    pub channel: String,
+    pub channel: crate::id::ChannelId,
     /// Timestamp of the updated message.
-    pub ts: String,
+    pub ts: crate::id::Ts,
+    /// Text of the updated message.
+    pub text: Option<String>,
+    /// The message after the update.
+    pub message: Option<crate::api::message::Message>,
 }
@@ -0,0 +1,152 @@
+//! Message model shared by Slack Web API responses.
+//!
+//! Slack returns the same message shape from `chat.postMessage`, `chat.update`
+//! and the conversation history/replies methods, so it lives here rather than
+//! next to any single method.
+
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::id::{TeamId, Ts, UserId};
+
+/// A message as returned by Slack.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Message {
+    /// Event type of the message, usually `message`.
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    /// Message subtype, e.g. `bot_message` or `thread_broadcast`.
+    pub subtype: Option<String>,
+    /// Timestamp of the message. Unique within its channel. Not included
+    /// in every response, e.g. the `message` of `chat.update`.
+    pub ts: Option<Ts>,
+    /// Text of the message.
+    pub text: Option<String>,
+    /// ID of the user who posted the message.
+    pub user: Option<UserId>,
+    /// ID of the bot that posted the message.
+    pub bot_id: Option<String>,
+    /// ID of the app that posted the message.
+    pub app_id: Option<String>,
+    /// Display name used when the message was posted with a custom `username`.
+    pub username: Option<String>,
+    /// Team ID of the author.
+    pub team: Option<TeamId>,
+    /// Structured blocks of the message.
+    pub blocks: Option<Vec<crate::blocks::Block>>,
+    /// Legacy secondary attachments of the message.
+    pub attachments: Option<Vec<crate::api::attachment::Attachment>>,
+    /// Timestamp of the thread parent, set on parents and replies alike.
+    pub thread_ts: Option<Ts>,
+    /// ID of the user who posted the thread parent. Only set on replies.
+    pub parent_user_id: Option<UserId>,
+    /// Number of replies in the thread. Only set on thread parents.
+    pub reply_count: Option<u32>,
+    /// Number of distinct users who replied in the thread.
+    pub reply_users_count: Option<u32>,
+    /// IDs of users who replied in the thread.
+    pub reply_users: Option<Vec<UserId>>,
+    /// Timestamp of the latest reply in the thread.
+    pub latest_reply: Option<Ts>,
+    /// Set when the message has been edited.
+    pub edited: Option<Edited>,
+    /// Reactions added to the message.
+    pub reactions: Option<Vec<Reaction>>,
+    /// Files shared in the message.
+    pub files: Option<Vec<File>>,
+    /// Message metadata. Decode a typed payload with `Metadata::into_typed`.
+    pub metadata: Option<crate::api::metadata::Metadata>,
+}
+
+/// Information about the last edit of a message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Edited {
+    /// ID of the user who edited the message.
+    pub user: UserId,
+    /// Timestamp of the edit.
+    pub ts: Ts,
+}
+
+/// A reaction on a message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reaction {
+    /// Emoji name, without colons.
+    pub name: String,
+    /// Number of users who reacted with this emoji.
+    pub count: u32,
+    /// IDs of users who reacted. May be truncated for popular reactions.
+    #[serde(default)]
+    pub users: Vec<UserId>,
+}
+
+/// A file shared in a message.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct File {
+    /// File ID.
+    pub id: String,
+    /// File name.
+    pub name: Option<String>,
+    /// Title of the file.
+    pub title: Option<String>,
+    /// MIME type of the file.
+    pub mimetype: Option<String>,
+    /// Slack's file type, e.g. `png` or `text`.
+    pub filetype: Option<String>,
+    /// Size of the file in bytes.
+    pub size: Option<u64>,
+    /// ID of the user who uploaded the file.
+    pub user: Option<UserId>,
+    /// Unix timestamp of when the file was created.
+    pub created: Option<i64>,
+    /// URL to the file contents. Requires a token to access.
+    pub url_private: Option<String>,
+    /// URL to download the file. Requires a token to access.
+    pub url_private_download: Option<String>,
+    /// Permalink to the file in Slack.
+    pub permalink: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_deserializes_full_payload() {
+        let raw = r#"{
+            "type": "message",
+            "subtype": "bot_message",
+            "ts": "1503435956.000247",
+            "text": "Here's a message for you",
+            "bot_id": "B123",
+            "blocks": [{"type": "divider"}],
+            "attachments": [{"color": "danger", "text": "failed", "ts": 1503435956}],
+            "thread_ts": "1503435956.000247",
+            "reply_count": 2,
+            "edited": {"user": "U123", "ts": "1503435960.000100"},
+            "reactions": [{"name": "tada", "count": 1, "users": ["U123"]}],
+            "files": [{"id": "F123", "name": "log.txt", "size": 42}],
+            "metadata": {"event_type": "incident", "event_payload": {"id": 7}}
+        }"#;
+        let msg: Message = serde_json::from_str(raw).unwrap();
+        assert_eq!(msg.kind.as_deref(), Some("message"));
+        assert_eq!(msg.ts, Some(Ts::new(1_503_435_956, 247)));
+        assert_eq!(msg.bot_id.as_deref(), Some("B123"));
+        assert_eq!(msg.blocks.as_ref().map(Vec::len), Some(1));
+        assert_eq!(msg.attachments.unwrap()[0].color.as_deref(), Some("danger"));
+        assert_eq!(msg.reply_count, Some(2));
+        assert_eq!(msg.edited.unwrap().user, "U123");
+        assert_eq!(msg.reactions.unwrap()[0].name, "tada");
+        assert_eq!(msg.files.unwrap()[0].size, Some(42));
+        assert_eq!(msg.metadata.unwrap().event_type, "incident");
+    }
+
+    #[test]
+    fn message_deserializes_minimal_payload() {
+        let msg: Message = serde_json::from_str(r#"{"ts":"1.2"}"#).unwrap();
+        assert_eq!(msg.ts, Some(Ts::new(1, 200_000)));
+        assert!(msg.text.is_none());
+        assert!(msg.reactions.is_none());
+    }
+}
@@ -0,0 +1,132 @@
+//! Message metadata.
+//!
+//! Metadata carries an application-defined `event_type` and a structured
+//! `event_payload`. It is attached when posting and returned on messages read
+//! back from Slack.
+
+use serde::de::DeserializeOwned;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json as json;
+
+/// Message metadata with a typed `event_payload`.
+///
+/// Request builders take metadata with any serializable payload, e.g.
+/// `PostMessage::new(channel).metadata(Metadata::new("incident_created", incident))`.
+/// Metadata read back from Slack is untyped, `Metadata<json::Value>`; decode
+/// it with [`Metadata::into_typed`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Metadata<T = json::Value> {
+    /// Application-defined name of the event, e.g. `incident_created`.
+    pub event_type: String,
+    /// Structured payload of the event.
+    pub event_payload: T,
+}
+
+impl<T> Metadata<T> {
+    /// Creates metadata for the given event type and payload.
+    #[must_use]
+    pub fn new(event_type: impl Into<String>, event_payload: T) -> Self {
+        Self {
+            event_type: event_type.into(),
+            event_payload,
+        }
+    }
+
+    /// Whether this is [`Metadata::empty`], which is sent as `{}`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.event_type.is_empty()
+    }
+}
+
+impl<T: Serialize> Serialize for Metadata<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.is_empty() {
+            return serializer.serialize_struct("Metadata", 0)?.end();
+        }
+        let mut state = serializer.serialize_struct("Metadata", 2)?;
+        state.serialize_field("event_type", &self.event_type)?;
+        state.serialize_field("event_payload", &self.event_payload)?;
+        state.end()
+    }
+}
+
+impl<T: Serialize> Metadata<T> {
+    /// Serializes the payload so the metadata can be attached to a message.
+    ///
+    /// # Errors
+    /// Returns a serialization error if the payload cannot be encoded as JSON.
+    pub fn into_untyped(self) -> json::Result<Metadata> {
+        Ok(Metadata {
+            event_type: self.event_type,
+            event_payload: json::to_value(self.event_payload)?,
+        })
+    }
+}
+
+impl Metadata {
+    /// Metadata with no event, sent as an empty object. Passing it to
+    /// `chat.update` removes the message's previous metadata.
+    #[must_use]
+    pub fn empty() -> Self {
+        Self::new(String::new(), json::Value::Null)
+    }
+
+    /// Decodes the payload into `T`.
+    ///
+    /// # Errors
+    /// Returns a deserialization error if the payload does not match `T`.
+    pub fn into_typed<T: DeserializeOwned>(self) -> json::Result<Metadata<T>> {
+        Ok(Metadata {
+            event_type: self.event_type,
+            event_payload: json::from_value(self.event_payload)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Incident {
+        id: String,
+        severity: u8,
+    }
+
+    #[test]
+    fn typed_metadata_round_trips() {
+        let typed = Metadata::new(
+            "incident_created",
+            Incident {
+                id: "INC-42".into(),
+                severity: 2,
+            },
+        );
+        let untyped = typed.into_untyped().unwrap();
+        let json = serde_json::to_string(&untyped).unwrap();
+        assert_eq!(
+            json,
+            r#"{"event_type":"incident_created","event_payload":{"id":"INC-42","severity":2}}"#
+        );
+
+        let decoded: Metadata = serde_json::from_str(&json).unwrap();
+        let typed: Metadata<Incident> = decoded.into_typed().unwrap();
+        assert_eq!(typed.event_payload.id, "INC-42");
+        assert_eq!(typed.event_payload.severity, 2);
+    }
+
+    #[test]
+    fn empty_metadata_serializes_as_empty_object() {
+        let empty = Metadata::empty();
+        assert!(empty.is_empty());
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "{}");
+    }
+
+    #[test]
+    fn into_typed_reports_mismatched_payload() {
+        let untyped = Metadata::new("incident_created", serde_json::json!({"id": 1}));
+        assert!(untyped.into_typed::<Incident>().is_err());
+    }
+}
@@ -1 +1,5 @@
+pub mod attachment;
 pub mod chat;
+pub mod message;
+pub mod metadata;
+pub mod reactions;
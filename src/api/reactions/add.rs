@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+/// Adds a reaction to an item.
+///
+/// Bot token: reactions:write
+/// User token: reactions:write
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Default)]
+#[slaq_macros::slack_api(
+    path="/reactions.add",
+    chat_method=add,
+    response=AddResponse,
+    call_alias="AddCall"
+)]
+pub struct Add {
+    /// Channel where the message to add reaction to was posted.
+    pub channel: crate::id::ChannelId,
+    /// Reaction (emoji) name, without colons.
+    pub name: String,
+    /// Timestamp of the message to add reaction to.
+    pub timestamp: crate::id::Ts,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+/// Response for `reactions.add`.
+pub struct AddResponse {}
@@ -0,0 +1,431 @@
+//! `CommonMark` to rich text conversion.
+
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+
+use crate::mrkdwn::{Document, Node, parse_mentions};
+
+use super::{
+    ListStyle, RichText, RichTextElement, RichTextInline, RichTextList, RichTextPreformatted,
+    RichTextQuote, RichTextSection, TextStyle,
+};
+
+impl RichText {
+    /// Converts `CommonMark` into a `rich_text` block.
+    ///
+    /// Paragraphs and headings become sections, with headings in bold.
+    /// Emphasis, strong, strikethrough and inline code become text styles,
+    /// links and images become links, nested lists become `rich_text_list`
+    /// elements with increasing `indent`, code blocks become preformatted
+    /// elements and block quotes become quotes. Slack mentions such as
+    /// `<@U123>`, `<#C123>`, `<!here>` and `<!subteam^S123>`, `<!date^..>`
+    /// tokens and `:emoji:` codes are turned into their own elements.
+    ///
+    /// Thematic breaks have no rich text equivalent and are dropped.
+    #[must_use]
+    pub fn from_commonmark(input: &str) -> Self {
+        let mut converter = Converter::default();
+        for event in Parser::new_ext(input, Options::ENABLE_STRIKETHROUGH) {
+            converter.event(event);
+        }
+        RichText::new(converter.finish())
+    }
+}
+
+/// A list being converted, tracking item numbers across nested lists.
+struct ListLevel {
+    style: ListStyle,
+    start: u32,
+    items: u32,
+}
+
+#[derive(Default)]
+struct Converter {
+    out: Vec<RichTextElement>,
+    inline: Vec<RichTextInline>,
+    bold: u32,
+    italic: u32,
+    strike: u32,
+    /// URL and label of the link being read.
+    link: Option<(String, String)>,
+    lists: Vec<ListLevel>,
+    /// Items of the innermost list not yet emitted.
+    chunk: Option<RichTextList>,
+    quotes: u32,
+    code: Option<String>,
+    /// Set when a paragraph ended inside a list item or quote.
+    needs_break: bool,
+}
+
+impl Converter {
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            // `<!here>` and friends parse as HTML declarations.
+            Event::Text(text) | Event::Html(text) | Event::InlineHtml(text) => self.text(&text),
+            Event::Code(code) => {
+                if let Some((_, label)) = &mut self.link {
+                    label.push_str(&code);
+                } else {
+                    let style = TextStyle {
+                        code: Some(true),
+                        ..self.style()
+                    };
+                    self.push_text(&code, style);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => self.text("\n"),
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading { .. } | Tag::Strong => self.bold += 1,
+            Tag::Emphasis => self.italic += 1,
+            Tag::Strikethrough => self.strike += 1,
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.link = Some((dest_url.into_string(), String::new()));
+            }
+            Tag::BlockQuote(_) => {
+                if self.lists.is_empty() {
+                    self.flush();
+                }
+                self.quotes += 1;
+            }
+            Tag::CodeBlock(_) => self.code = Some(String::new()),
+            Tag::List(start) => {
+                self.flush();
+                self.lists.push(ListLevel {
+                    style: if start.is_some() {
+                        ListStyle::Ordered
+                    } else {
+                        ListStyle::Bullet
+                    },
+                    start: start.map_or(0, |n| u32::try_from(n.saturating_sub(1)).unwrap_or(0)),
+                    items: 0,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph => self.end_paragraph(),
+            TagEnd::Heading(_) => {
+                self.bold -= 1;
+                self.end_paragraph();
+            }
+            TagEnd::Strong => self.bold -= 1,
+            TagEnd::Emphasis => self.italic -= 1,
+            TagEnd::Strikethrough => self.strike -= 1,
+            TagEnd::Link | TagEnd::Image => {
+                if let Some((url, label)) = self.link.take() {
+                    let style = self.style();
+                    self.push_inline(RichTextInline::Link {
+                        text: (!label.is_empty() && label != url).then_some(label),
+                        url,
+                        style: (!style.is_plain()).then_some(style),
+                    });
+                }
+            }
+            TagEnd::BlockQuote(_) => {
+                if self.lists.is_empty() {
+                    self.flush();
+                } else {
+                    self.needs_break = true;
+                }
+                self.quotes -= 1;
+            }
+            TagEnd::CodeBlock => {
+                let mut code = self.code.take().unwrap_or_default();
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+                if self.lists.is_empty() && self.quotes == 0 {
+                    self.push_block(RichTextElement::Preformatted(RichTextPreformatted::new(
+                        vec![RichTextInline::text(code)],
+                    )));
+                } else {
+                    let style = TextStyle {
+                        code: Some(true),
+                        ..TextStyle::default()
+                    };
+                    self.push_text(&code, style);
+                    self.needs_break = true;
+                }
+            }
+            TagEnd::Item => self.flush(),
+            TagEnd::List(_) => {
+                self.flush();
+                self.lists.pop();
+                if let Some(chunk) = self.chunk.take() {
+                    self.out.push(RichTextElement::List(chunk));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Vec<RichTextElement> {
+        self.flush();
+        if let Some(chunk) = self.chunk.take() {
+            self.out.push(RichTextElement::List(chunk));
+        }
+        self.out
+    }
+
+    fn style(&self) -> TextStyle {
+        TextStyle {
+            bold: (self.bold > 0).then_some(true),
+            italic: (self.italic > 0).then_some(true),
+            strike: (self.strike > 0).then_some(true),
+            code: None,
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some(code) = &mut self.code {
+            code.push_str(text);
+        } else if let Some((_, label)) = &mut self.link {
+            label.push_str(text);
+        } else {
+            self.push_text(text, self.style());
+        }
+    }
+
+    fn end_paragraph(&mut self) {
+        if self.lists.is_empty() && self.quotes == 0 {
+            self.flush();
+        } else {
+            self.needs_break = true;
+        }
+    }
+
+    fn line_break(&mut self) {
+        if std::mem::take(&mut self.needs_break) && !self.inline.is_empty() {
+            self.append("\n", None);
+        }
+    }
+
+    /// Appends text, merging it into the previous element if the style matches.
+    fn push_text(&mut self, text: &str, style: TextStyle) {
+        self.line_break();
+        self.append(text, (!style.is_plain()).then_some(style));
+    }
+
+    fn append(&mut self, text: &str, style: Option<TextStyle>) {
+        if let Some(RichTextInline::Text {
+            text: last,
+            style: last_style,
+        }) = self.inline.last_mut()
+            && *last_style == style
+        {
+            last.push_str(text);
+            return;
+        }
+        self.inline.push(RichTextInline::Text {
+            text: text.to_owned(),
+            style,
+        });
+    }
+
+    fn push_inline(&mut self, element: RichTextInline) {
+        self.line_break();
+        self.inline.push(element);
+    }
+
+    fn push_block(&mut self, element: RichTextElement) {
+        if let Some(chunk) = self.chunk.take() {
+            self.out.push(RichTextElement::List(chunk));
+        }
+        self.out.push(element);
+    }
+
+    /// Emits the pending inline elements as a list item, quote or section.
+    fn flush(&mut self) {
+        self.needs_break = false;
+        let elements: Vec<_> = std::mem::take(&mut self.inline)
+            .into_iter()
+            .flat_map(expand_entities)
+            .collect();
+        if elements.is_empty() {
+            return;
+        }
+        let section = RichTextSection::new(elements);
+        let depth = u32::try_from(self.lists.len()).unwrap_or(u32::MAX);
+        let border = (self.quotes > 0).then_some(1);
+        if let Some(level) = self.lists.last_mut() {
+            let indent = depth - 1;
+            let (style, offset) = (level.style, level.start + level.items);
+            level.items += 1;
+            if let Some(chunk) = &mut self.chunk
+                && chunk.indent.unwrap_or(0) == indent
+                && chunk.style == style
+            {
+                chunk.elements.push(section);
+                return;
+            }
+            if let Some(chunk) = self.chunk.take() {
+                self.out.push(RichTextElement::List(chunk));
+            }
+            self.chunk = Some(RichTextList {
+                style,
+                elements: vec![section],
+                indent: (indent > 0).then_some(indent),
+                offset: (style == ListStyle::Ordered && offset > 0).then_some(offset),
+                border,
+            });
+        } else if self.quotes > 0 {
+            self.push_block(RichTextElement::Quote(RichTextQuote::new(section.elements)));
+        } else {
+            self.push_block(RichTextElement::Section(section));
+        }
+    }
+}
+
+/// Splits Slack entities and emoji codes out of unstyled and styled text.
+fn expand_entities(element: RichTextInline) -> Vec<RichTextInline> {
+    let RichTextInline::Text { text, style } = element else {
+        return vec![element];
+    };
+    if style.is_some_and(|s| s.code == Some(true)) {
+        return vec![RichTextInline::Text { text, style }];
+    }
+    let is_id = |id: &str| !id.is_empty() && !id.contains(char::is_whitespace);
+    let mut out: Vec<RichTextInline> = Vec::new();
+    for node in parse_mentions(&text) {
+        let element = match node {
+            Node::Text(text) => RichTextInline::Text { text, style },
+            Node::User { id, .. } if is_id(&id) => RichTextInline::User {
+                user_id: id.into(),
+                style,
+            },
+            Node::Channel { id, .. } if is_id(&id) => RichTextInline::Channel {
+                channel_id: id.into(),
+                style,
+            },
+            Node::Usergroup { id, .. } if is_id(&id) => RichTextInline::Usergroup {
+                usergroup_id: id,
+                style,
+            },
+            Node::Broadcast { range, .. } => RichTextInline::Broadcast {
+                range: range.as_str().to_owned(),
+            },
+            Node::Date {
+                timestamp,
+                format,
+                url,
+                fallback,
+            } => RichTextInline::Date {
+                timestamp,
+                format,
+                url,
+                fallback: (!fallback.is_empty()).then_some(fallback),
+            },
+            Node::Emoji(name) => RichTextInline::emoji(name),
+            // Entities without a rich text element stay as text.
+            other => RichTextInline::Text {
+                text: Document { nodes: vec![other] }.to_mrkdwn(),
+                style,
+            },
+        };
+        if let RichTextInline::Text { text, .. } = &element
+            && let Some(RichTextInline::Text { text: last, .. }) = out.last_mut()
+        {
+            last.push_str(text);
+        } else {
+            out.push(element);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn convert(input: &str) -> serde_json::Value {
+        serde_json::to_value(RichText::from_commonmark(input).build()).unwrap()["elements"].clone()
+    }
+
+    #[test]
+    fn paragraphs_and_headings_become_styled_sections() {
+        assert_eq!(
+            convert("# Release\n\nShipped **fast** and _safe_, ~~late~~ `v2`"),
+            json!([
+                {"type": "rich_text_section", "elements": [
+                    {"type": "text", "text": "Release", "style": {"bold": true}}
+                ]},
+                {"type": "rich_text_section", "elements": [
+                    {"type": "text", "text": "Shipped "},
+                    {"type": "text", "text": "fast", "style": {"bold": true}},
+                    {"type": "text", "text": " and "},
+                    {"type": "text", "text": "safe", "style": {"italic": true}},
+                    {"type": "text", "text": ", "},
+                    {"type": "text", "text": "late", "style": {"strike": true}},
+                    {"type": "text", "text": " "},
+                    {"type": "text", "text": "v2", "style": {"code": true}}
+                ]}
+            ])
+        );
+    }
+
+    #[test]
+    fn nested_lists_are_flattened_with_indent_and_offset() {
+        assert_eq!(
+            convert("1. one\n   - a\n   - b\n2. two\n"),
+            json!([
+                {"type": "rich_text_list", "style": "ordered", "elements": [
+                    {"type": "rich_text_section", "elements": [{"type": "text", "text": "one"}]}
+                ]},
+                {"type": "rich_text_list", "style": "bullet", "indent": 1, "elements": [
+                    {"type": "rich_text_section", "elements": [{"type": "text", "text": "a"}]},
+                    {"type": "rich_text_section", "elements": [{"type": "text", "text": "b"}]}
+                ]},
+                {"type": "rich_text_list", "style": "ordered", "offset": 1, "elements": [
+                    {"type": "rich_text_section", "elements": [{"type": "text", "text": "two"}]}
+                ]}
+            ])
+        );
+    }
+
+    #[test]
+    fn code_blocks_and_quotes() {
+        assert_eq!(
+            convert("> first\n> second\n\n```\nlet x = 1;\n```\n"),
+            json!([
+                {"type": "rich_text_quote", "elements": [
+                    {"type": "text", "text": "first\nsecond"}
+                ]},
+                {"type": "rich_text_preformatted", "elements": [
+                    {"type": "text", "text": "let x = 1;"}
+                ]}
+            ])
+        );
+    }
+
+    #[test]
+    fn links_mentions_and_emoji_become_elements() {
+        assert_eq!(
+            convert(
+                "Hey <@U123> <!here>, see [docs](https://example.com) in <#C1|general> :wave: at 10:30:00"
+            ),
+            json!([{"type": "rich_text_section", "elements": [
+                {"type": "text", "text": "Hey "},
+                {"type": "user", "user_id": "U123"},
+                {"type": "text", "text": " "},
+                {"type": "broadcast", "range": "here"},
+                {"type": "text", "text": ", see "},
+                {"type": "link", "url": "https://example.com", "text": "docs"},
+                {"type": "text", "text": " in "},
+                {"type": "channel", "channel_id": "C1"},
+                {"type": "text", "text": " "},
+                {"type": "emoji", "name": "wave"},
+                {"type": "text", "text": " at 10:30:00"}
+            ]}])
+        );
+    }
+}
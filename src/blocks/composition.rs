@@ -0,0 +1,334 @@
+//! Composition objects used inside blocks.
+
+use serde::{Deserialize, Serialize, Serializer};
+
+/// A text object: either plain text or mrkdwn.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Text {
+    /// Plain text, rendered as-is.
+    PlainText(PlainText),
+    /// Text formatted with Slack's mrkdwn.
+    Mrkdwn(MrkdwnText),
+}
+
+impl Text {
+    /// Creates a `plain_text` object.
+    #[must_use]
+    pub fn plain(text: impl Into<String>) -> Self {
+        Self::PlainText(PlainText::new(text))
+    }
+
+    /// Creates a `mrkdwn` text object.
+    #[must_use]
+    pub fn mrkdwn(text: impl Into<String>) -> Self {
+        Self::Mrkdwn(MrkdwnText::new(text))
+    }
+}
+
+// Each text object carries its own `type` tag, so serialization delegates to it.
+impl Serialize for Text {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::PlainText(text) => text.serialize(serializer),
+            Self::Mrkdwn(text) => text.serialize(serializer),
+        }
+    }
+}
+
+/// A `plain_text` text object.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "plain_text")]
+#[slaq_macros::builder]
+pub struct PlainText {
+    /// The text to display.
+    pub text: String,
+    /// Whether emoji shortcodes like `:smile:` are rendered as emoji.
+    pub emoji: Option<bool>,
+}
+
+/// A `mrkdwn` text object.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "mrkdwn")]
+#[slaq_macros::builder]
+pub struct MrkdwnText {
+    /// The mrkdwn-formatted text to display.
+    pub text: String,
+    /// When `true`, URLs, channel names and mentions are not auto-linked.
+    pub verbatim: Option<bool>,
+}
+
+impl From<&str> for PlainText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for PlainText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<&str> for MrkdwnText {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for MrkdwnText {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Strings convert to `plain_text`; use [`Text::mrkdwn`] for mrkdwn.
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Self::plain(text)
+    }
+}
+
+impl From<PlainText> for Text {
+    fn from(text: PlainText) -> Self {
+        Self::PlainText(text)
+    }
+}
+
+impl From<MrkdwnText> for Text {
+    fn from(text: MrkdwnText) -> Self {
+        Self::Mrkdwn(text)
+    }
+}
+
+/// A reference to an image hosted in Slack, by URL or file ID.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[slaq_macros::builder]
+pub struct SlackFile {
+    /// URL of the file, e.g. its `url_private` or `permalink`.
+    pub url: Option<String>,
+    /// ID of the file.
+    pub id: Option<String>,
+}
+
+/// A single selectable item in a select menu, overflow menu, checkbox group
+/// or radio button group.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct OptionObject {
+    /// The text shown for the option. Must be `plain_text` in menus. Maximum length is 75 characters.
+    pub text: Text,
+    /// The value sent in the interaction payload. Maximum length is 150 characters.
+    pub value: String,
+    /// A line of descriptive text shown below the option. Maximum length is 75 characters.
+    pub description: Option<Text>,
+    /// A URL to load in the user's browser. Only valid in overflow menus. Maximum length is 3000 characters.
+    pub url: Option<String>,
+}
+
+/// A labelled group of options in a select menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct OptionGroup {
+    /// The label shown above the group. Maximum length is 75 characters.
+    pub label: PlainText,
+    /// The options in the group. Maximum 100 items.
+    pub options: Vec<OptionObject>,
+}
+
+/// A dialog asking the user to confirm before an interactive element acts.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct ConfirmationDialog {
+    /// The dialog title. Maximum length is 100 characters.
+    pub title: PlainText,
+    /// The explanatory text. Maximum length is 300 characters.
+    pub text: PlainText,
+    /// The label of the confirm button. Maximum length is 30 characters.
+    pub confirm: PlainText,
+    /// The label of the cancel button. Maximum length is 30 characters.
+    pub deny: PlainText,
+    /// The color scheme of the confirm button. Defaults to `primary`.
+    pub style: Option<ButtonStyle>,
+}
+
+/// Visual style of a button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ButtonStyle {
+    /// Green, for affirmative actions. Use sparingly.
+    Primary,
+    /// Red, for destructive actions.
+    Danger,
+}
+
+/// Filters the conversations listed in a conversations select menu.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[slaq_macros::builder]
+pub struct ConversationFilter {
+    /// Conversation types to include. Defaults to all types.
+    pub include: Option<Vec<ConversationType>>,
+    /// Whether to hide externally shared channels.
+    pub exclude_external_shared_channels: Option<bool>,
+    /// Whether to hide conversations with bot users.
+    pub exclude_bot_users: Option<bool>,
+}
+
+/// A conversation type used by `ConversationFilter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationType {
+    /// Direct messages.
+    Im,
+    /// Group direct messages.
+    Mpim,
+    /// Private channels.
+    Private,
+    /// Public channels.
+    Public,
+}
+
+/// Determines when a plain-text input dispatches a `block_actions` payload.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[slaq_macros::builder]
+pub struct DispatchActionConfig {
+    /// The interactions that dispatch the payload.
+    pub trigger_actions_on: Option<Vec<TriggerAction>>,
+}
+
+/// An interaction that dispatches a `block_actions` payload from an input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerAction {
+    /// The user presses Enter.
+    OnEnterPressed,
+    /// The user types a character.
+    OnCharacterEntered,
+}
+
+/// A link trigger that starts a workflow.
+#[serde_with::skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct Trigger {
+    /// The link trigger URL.
+    pub url: String,
+    /// Values for the trigger's customizable input parameters.
+    pub customizable_input_parameters: Option<Vec<InputParameter>>,
+}
+
+/// A name/value pair passed to a workflow trigger.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct InputParameter {
+    /// Name of the input parameter.
+    pub name: String,
+    /// Value of the input parameter.
+    pub value: String,
+}
+
+/// A workflow started by a workflow button.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[slaq_macros::builder]
+pub struct Workflow {
+    /// The trigger that starts the workflow.
+    pub trigger: Trigger,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_objects_round_trip_with_type_tag() {
+        let json = serde_json::to_string(&Text::plain("hi")).unwrap();
+        assert_eq!(json, r#"{"type":"plain_text","text":"hi"}"#);
+        let json = serde_json::to_string(&Text::mrkdwn("*hi*")).unwrap();
+        assert_eq!(json, r#"{"type":"mrkdwn","text":"*hi*"}"#);
+
+        let text: Text = serde_json::from_str(&json).unwrap();
+        assert!(matches!(text, Text::Mrkdwn(t) if t.text == "*hi*"));
+    }
+
+    #[test]
+    fn text_options_and_conversions() {
+        let plain: Text = PlainText::new("hi :wave:").emoji(true).into();
+        assert_eq!(
+            serde_json::to_string(&plain).unwrap(),
+            r#"{"type":"plain_text","text":"hi :wave:","emoji":true}"#
+        );
+        let mrkdwn: Text = MrkdwnText::new("<#C123>").verbatim(true).into();
+        assert_eq!(
+            serde_json::to_string(&mrkdwn).unwrap(),
+            r#"{"type":"mrkdwn","text":"<#C123>","verbatim":true}"#
+        );
+        assert!(matches!(Text::from("x"), Text::PlainText(_)));
+        assert_eq!(PlainText::from("x").text, "x");
+    }
+
+    #[test]
+    fn options_and_groups_serialize() {
+        let group = OptionGroup::new(
+            "Fruit",
+            vec![OptionObject::new("Apple", "apple").description(Text::mrkdwn("_red_"))],
+        );
+        assert_eq!(
+            serde_json::to_value(&group).unwrap(),
+            serde_json::json!({
+                "label": {"type": "plain_text", "text": "Fruit"},
+                "options": [{
+                    "text": {"type": "plain_text", "text": "Apple"},
+                    "value": "apple",
+                    "description": {"type": "mrkdwn", "text": "_red_"}
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn confirm_filter_dispatch_and_workflow_serialize() {
+        let confirm = ConfirmationDialog::new("Sure?", "This deletes it.", "Delete", "Keep")
+            .style(ButtonStyle::Danger);
+        let value = serde_json::to_value(&confirm).unwrap();
+        assert_eq!(value["title"]["type"], "plain_text");
+        assert_eq!(value["style"], "danger");
+
+        let filter = ConversationFilter::new()
+            .include(vec![ConversationType::Public, ConversationType::Mpim])
+            .exclude_bot_users(true);
+        assert_eq!(
+            serde_json::to_string(&filter).unwrap(),
+            r#"{"include":["public","mpim"],"exclude_bot_users":true}"#
+        );
+
+        let dispatch =
+            DispatchActionConfig::new().trigger_actions_on(vec![TriggerAction::OnEnterPressed]);
+        assert_eq!(
+            serde_json::to_string(&dispatch).unwrap(),
+            r#"{"trigger_actions_on":["on_enter_pressed"]}"#
+        );
+
+        let workflow = Workflow::new(
+            Trigger::new("https://slack.com/shortcuts/Ft0123/abc")
+                .customizable_input_parameters(vec![InputParameter::new("user", "U123")]),
+        );
+        let value = serde_json::to_value(&workflow).unwrap();
+        assert_eq!(
+            value["trigger"]["customizable_input_parameters"][0]["name"],
+            "user"
+        );
+    }
+}
@@ -0,0 +1,568 @@
+//! Interactive elements for `actions`, `input` and section accessories.
+
+use super::{
+    ButtonStyle, ConfirmationDialog, ConversationFilter, DispatchActionConfig, OptionGroup,
+    OptionObject, PlainText, SlackFile, Workflow,
+};
+
+#[slaq_macros::element(kind = "button")]
+#[derive(Debug, Clone)]
+/// A button that sends an interaction payload or opens a URL.
+pub struct Button {
+    /// The button label. Maximum length is 75 characters.
+    pub text: PlainText,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// A URL to load in the user's browser. Maximum length is 3000 characters.
+    pub url: Option<String>,
+    /// The value sent in the interaction payload. Maximum length is 2000 characters.
+    pub value: Option<String>,
+    /// Visual style of the button.
+    pub style: Option<ButtonStyle>,
+    /// A dialog shown after the button is clicked.
+    pub confirm: Option<ConfirmationDialog>,
+    /// A label read out by screen readers. Maximum length is 75 characters.
+    pub accessibility_label: Option<String>,
+}
+
+#[slaq_macros::element(kind = "workflow_button")]
+#[derive(Debug, Clone)]
+/// A button that starts a workflow.
+pub struct WorkflowButton {
+    /// The button label. Maximum length is 75 characters.
+    pub text: PlainText,
+    /// The workflow to start.
+    pub workflow: Workflow,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// Visual style of the button.
+    pub style: Option<ButtonStyle>,
+    /// A label read out by screen readers. Maximum length is 75 characters.
+    pub accessibility_label: Option<String>,
+}
+
+#[slaq_macros::element(kind = "static_select")]
+#[derive(Debug, Clone)]
+/// A select menu with a static list of options.
+pub struct StaticSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The options to choose from. Maximum 100 items. One of `options` or `option_groups` is required.
+    pub options: Option<Vec<OptionObject>>,
+    /// Groups of options to choose from. Maximum 100 groups.
+    pub option_groups: Option<Vec<OptionGroup>>,
+    /// The option selected when the menu loads.
+    pub initial_option: Option<OptionObject>,
+    /// A dialog shown after an option is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "multi_static_select")]
+#[derive(Debug, Clone)]
+/// A multi-select menu with a static list of options.
+pub struct MultiStaticSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The options to choose from. Maximum 100 items. One of `options` or `option_groups` is required.
+    pub options: Option<Vec<OptionObject>>,
+    /// Groups of options to choose from. Maximum 100 groups.
+    pub option_groups: Option<Vec<OptionGroup>>,
+    /// The options selected when the menu loads.
+    pub initial_options: Option<Vec<OptionObject>>,
+    /// A dialog shown after an option is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Maximum number of items that can be selected. Minimum 1.
+    pub max_selected_items: Option<u32>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "external_select")]
+#[derive(Debug, Clone)]
+/// A select menu whose options are loaded from your app.
+pub struct ExternalSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The option selected when the menu loads.
+    pub initial_option: Option<OptionObject>,
+    /// Characters typed before options are requested. Defaults to 3.
+    pub min_query_length: Option<u32>,
+    /// A dialog shown after an option is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "multi_external_select")]
+#[derive(Debug, Clone)]
+/// A multi-select menu whose options are loaded from your app.
+pub struct MultiExternalSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The options selected when the menu loads.
+    pub initial_options: Option<Vec<OptionObject>>,
+    /// Characters typed before options are requested. Defaults to 3.
+    pub min_query_length: Option<u32>,
+    /// A dialog shown after an option is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Maximum number of items that can be selected. Minimum 1.
+    pub max_selected_items: Option<u32>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "users_select")]
+#[derive(Debug, Clone)]
+/// A select menu listing users in the workspace.
+pub struct UsersSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The user ID selected when the menu loads.
+    pub initial_user: Option<String>,
+    /// A dialog shown after a user is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "multi_users_select")]
+#[derive(Debug, Clone)]
+/// A multi-select menu listing users in the workspace.
+pub struct MultiUsersSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The user IDs selected when the menu loads.
+    pub initial_users: Option<Vec<String>>,
+    /// A dialog shown after a user is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Maximum number of items that can be selected. Minimum 1.
+    pub max_selected_items: Option<u32>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "conversations_select")]
+#[derive(Debug, Clone)]
+/// A select menu listing conversations: channels, DMs and group DMs.
+pub struct ConversationsSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The conversation ID selected when the menu loads.
+    pub initial_conversation: Option<String>,
+    /// Pre-select the conversation the view was opened from.
+    pub default_to_current_conversation: Option<bool>,
+    /// A dialog shown after a conversation is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the view submission includes a `response_url`. Only in input blocks of modals.
+    pub response_url_enabled: Option<bool>,
+    /// Filters the listed conversations.
+    pub filter: Option<ConversationFilter>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "multi_conversations_select")]
+#[derive(Debug, Clone)]
+/// A multi-select menu listing conversations: channels, DMs and group DMs.
+pub struct MultiConversationsSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The conversation IDs selected when the menu loads.
+    pub initial_conversations: Option<Vec<String>>,
+    /// Pre-select the conversation the view was opened from.
+    pub default_to_current_conversation: Option<bool>,
+    /// A dialog shown after a conversation is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Maximum number of items that can be selected. Minimum 1.
+    pub max_selected_items: Option<u32>,
+    /// Filters the listed conversations.
+    pub filter: Option<ConversationFilter>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "channels_select")]
+#[derive(Debug, Clone)]
+/// A select menu listing public channels.
+pub struct ChannelsSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The channel ID selected when the menu loads.
+    pub initial_channel: Option<String>,
+    /// A dialog shown after a channel is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the view submission includes a `response_url`. Only in input blocks of modals.
+    pub response_url_enabled: Option<bool>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "multi_channels_select")]
+#[derive(Debug, Clone)]
+/// A multi-select menu listing public channels.
+pub struct MultiChannelsSelect {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The channel IDs selected when the menu loads.
+    pub initial_channels: Option<Vec<String>>,
+    /// A dialog shown after a channel is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Maximum number of items that can be selected. Minimum 1.
+    pub max_selected_items: Option<u32>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "overflow")]
+#[derive(Debug, Clone)]
+/// A compact menu of up to five options, shown behind a "…" button.
+pub struct Overflow {
+    /// The options in the menu. Between 1 and 5 items.
+    pub options: Vec<OptionObject>,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// A dialog shown after an option is selected.
+    pub confirm: Option<ConfirmationDialog>,
+}
+
+#[slaq_macros::element(kind = "checkboxes")]
+#[derive(Debug, Clone)]
+/// A group of checkboxes.
+pub struct Checkboxes {
+    /// The checkboxes. Maximum 10 items.
+    pub options: Vec<OptionObject>,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The options checked when the group loads. Must exactly match entries of `options`.
+    pub initial_options: Option<Vec<OptionObject>>,
+    /// A dialog shown after a checkbox is clicked.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+}
+
+#[slaq_macros::element(kind = "radio_buttons")]
+#[derive(Debug, Clone)]
+/// A group of radio buttons.
+pub struct RadioButtons {
+    /// The radio buttons. Maximum 10 items.
+    pub options: Vec<OptionObject>,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The option selected when the group loads. Must exactly match an entry of `options`.
+    pub initial_option: Option<OptionObject>,
+    /// A dialog shown after a radio button is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+}
+
+#[slaq_macros::element(kind = "datepicker")]
+#[derive(Debug, Clone)]
+/// A calendar picker for a single date.
+pub struct DatePicker {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The date selected when the picker loads, formatted `YYYY-MM-DD`.
+    pub initial_date: Option<String>,
+    /// A dialog shown after a date is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "timepicker")]
+#[derive(Debug, Clone)]
+/// A picker for a time of day.
+pub struct TimePicker {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The time selected when the picker loads, formatted `HH:mm` in 24-hour time.
+    pub initial_time: Option<String>,
+    /// A dialog shown after a time is selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when nothing is selected. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+    /// IANA time zone shown with the picker, e.g. `America/Chicago`.
+    pub timezone: Option<String>,
+}
+
+#[slaq_macros::element(kind = "datetimepicker")]
+#[derive(Debug, Clone)]
+/// A picker for a date and a time of day.
+pub struct DatetimePicker {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The Unix timestamp selected when the picker loads.
+    pub initial_date_time: Option<i64>,
+    /// A dialog shown after a date and time are selected.
+    pub confirm: Option<ConfirmationDialog>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+}
+
+#[slaq_macros::element(kind = "plain_text_input")]
+#[derive(Debug, Clone)]
+/// A single- or multi-line free-text input.
+pub struct PlainTextInput {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The text filled in when the input loads.
+    pub initial_value: Option<String>,
+    /// Whether the input spans multiple lines.
+    pub multiline: Option<bool>,
+    /// Minimum length of the input. Maximum 3000.
+    pub min_length: Option<u32>,
+    /// Maximum length of the input.
+    pub max_length: Option<u32>,
+    /// When the input dispatches a `block_actions` payload.
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when the input is empty. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "email_text_input")]
+#[derive(Debug, Clone)]
+/// An input for an email address.
+pub struct EmailInput {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The email address filled in when the input loads.
+    pub initial_value: Option<String>,
+    /// When the input dispatches a `block_actions` payload.
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when the input is empty. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "url_text_input")]
+#[derive(Debug, Clone)]
+/// An input for a URL.
+pub struct UrlInput {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The URL filled in when the input loads.
+    pub initial_value: Option<String>,
+    /// When the input dispatches a `block_actions` payload.
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when the input is empty. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "number_input")]
+#[derive(Debug, Clone)]
+/// An input for a number.
+pub struct NumberInput {
+    /// Whether decimal numbers are accepted.
+    pub is_decimal_allowed: bool,
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// The number filled in when the input loads.
+    pub initial_value: Option<String>,
+    /// The minimum accepted value.
+    pub min_value: Option<String>,
+    /// The maximum accepted value.
+    pub max_value: Option<String>,
+    /// When the input dispatches a `block_actions` payload.
+    pub dispatch_action_config: Option<DispatchActionConfig>,
+    /// Whether the element is focused when the view opens.
+    pub focus_on_load: Option<bool>,
+    /// Text shown when the input is empty. Maximum length is 150 characters.
+    pub placeholder: Option<PlainText>,
+}
+
+#[slaq_macros::element(kind = "file_input")]
+#[derive(Debug, Clone)]
+/// An input for uploading files. Only in input blocks of modals.
+pub struct FileInput {
+    /// An identifier for the action. Maximum length is 255 characters.
+    pub action_id: Option<String>,
+    /// Accepted file extensions, e.g. `pdf`. Defaults to all types.
+    pub filetypes: Option<Vec<String>>,
+    /// Maximum number of files. Between 1 and 10, defaults to 10.
+    pub max_files: Option<u32>,
+}
+
+#[slaq_macros::element(kind = "image")]
+#[derive(Debug, Clone)]
+/// A small image, used in `context` blocks and as a section accessory.
+pub struct ImageElement {
+    /// A plain-text summary of the image.
+    pub alt_text: String,
+    /// The URL of the image. One of `image_url` or `slack_file` is required.
+    pub image_url: Option<String>,
+    /// A Slack-hosted image. One of `image_url` or `slack_file` is required.
+    pub slack_file: Option<SlackFile>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{Actions, Element, Input, Section, Text};
+    use serde_json::json;
+
+    #[test]
+    fn button_serializes_with_confirm() {
+        let button = Button::new("Deploy")
+            .action_id("deploy")
+            .value("v1.2.3")
+            .style(ButtonStyle::Primary)
+            .confirm(ConfirmationDialog::new(
+                "Deploy?",
+                "Ships to prod.",
+                "Ship",
+                "Wait",
+            ))
+            .build();
+        let value = serde_json::to_value(&button).unwrap();
+        assert_eq!(value["type"], "button");
+        assert_eq!(
+            value["text"],
+            json!({"type": "plain_text", "text": "Deploy"})
+        );
+        assert_eq!(value["action_id"], "deploy");
+        assert_eq!(value["style"], "primary");
+        assert_eq!(value["confirm"]["confirm"]["text"], "Ship");
+    }
+
+    #[test]
+    fn selects_carry_initial_values() {
+        let option = OptionObject::new("High", "high");
+        let select = StaticSelect::new()
+            .action_id("priority")
+            .options(vec![option.clone(), OptionObject::new("Low", "low")])
+            .initial_option(option)
+            .build();
+        let value = serde_json::to_value(&select).unwrap();
+        assert_eq!(value["type"], "static_select");
+        assert_eq!(value["initial_option"]["value"], "high");
+
+        let users = MultiUsersSelect::new()
+            .initial_users(vec!["U1".to_string(), "U2".to_string()])
+            .max_selected_items(2u32)
+            .build();
+        let value = serde_json::to_value(&users).unwrap();
+        assert_eq!(value["type"], "multi_users_select");
+        assert_eq!(value["initial_users"], json!(["U1", "U2"]));
+
+        let convos = ConversationsSelect::new()
+            .filter(ConversationFilter::new().exclude_bot_users(true))
+            .build();
+        let value = serde_json::to_value(&convos).unwrap();
+        assert_eq!(value["filter"]["exclude_bot_users"], true);
+    }
+
+    #[test]
+    fn pickers_and_inputs_use_slack_type_names() {
+        let kinds = [
+            DatePicker::new().initial_date("2025-01-31").build(),
+            TimePicker::new().initial_time("09:00").build(),
+            DatetimePicker::new()
+                .initial_date_time(1_700_000_000_i64)
+                .build(),
+            PlainTextInput::new().multiline(true).build(),
+            EmailInput::new().build(),
+            UrlInput::new().build(),
+            NumberInput::new(false).min_value("1").build(),
+            FileInput::new().max_files(1u32).build(),
+            Overflow::new(vec![OptionObject::new("Edit", "edit")]).build(),
+            Checkboxes::new(vec![OptionObject::new("A", "a")]).build(),
+            RadioButtons::new(vec![OptionObject::new("A", "a")]).build(),
+            MultiChannelsSelect::new().build(),
+        ]
+        .map(|e| e.kind().to_string());
+        assert_eq!(
+            kinds,
+            [
+                "datepicker",
+                "timepicker",
+                "datetimepicker",
+                "plain_text_input",
+                "email_text_input",
+                "url_text_input",
+                "number_input",
+                "file_input",
+                "overflow",
+                "checkboxes",
+                "radio_buttons",
+                "multi_channels_select",
+            ]
+        );
+    }
+
+    #[test]
+    fn elements_compose_with_layout_blocks() {
+        let actions = Actions::new(vec![
+            Button::new("Approve").action_id("approve").build(),
+            Overflow::new(vec![OptionObject::new("Details", "details")]).build(),
+        ])
+        .build();
+        let actions = serde_json::to_value(actions).unwrap();
+        assert_eq!(actions["elements"][1]["type"], "overflow");
+
+        let section = Section::new()
+            .text(Text::mrkdwn("Pick a date"))
+            .accessory(DatePicker::new().action_id("date").build())
+            .build();
+        let section = serde_json::to_value(section).unwrap();
+        assert_eq!(section["accessory"]["type"], "datepicker");
+
+        let input = Input::new("Reason", PlainTextInput::new().action_id("reason").build()).build();
+        let input = serde_json::to_value(input).unwrap();
+        assert_eq!(input["element"]["action_id"], "reason");
+    }
+
+    #[test]
+    fn elements_build_into_their_own_variant_and_read_back() {
+        let button = Button::new("Go")
+            .action_id("go")
+            .url("https://x.test")
+            .build();
+        assert!(
+            matches!(&button, Element::Button(b) if b.url.as_deref() == Some("https://x.test"))
+        );
+
+        let select = MultiStaticSelect::new()
+            .options(vec![OptionObject::new("A", "a")])
+            .max_selected_items(1u32)
+            .build();
+        let value = serde_json::to_value(&select).unwrap();
+        let Element::MultiStaticSelect(select) = serde_json::from_value(value).unwrap() else {
+            panic!("expected a multi_static_select");
+        };
+        assert_eq!(select.max_selected_items, Some(1));
+        assert_eq!(select.options.unwrap()[0].value, "a");
+    }
+}
@@ -0,0 +1,282 @@
+//! Plain-text and mrkdwn summaries of blocks.
+//!
+//! Slack shows a message's top-level `text` in notifications and in clients
+//! that can't display blocks. `fallback_text` derives that text from headers,
+//! section text and fields, context text, `markdown` blocks and rich text.
+//! Interactive blocks, images and dividers have no text and are skipped.
+
+use crate::mrkdwn::{Document, escape, wrap};
+
+use super::{Block, Element, ListStyle, RichTextElement, RichTextInline, Text, TextStyle};
+
+/// The markup of rendered text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextFormat {
+    /// Slack mrkdwn, for a message's `text`.
+    #[default]
+    Mrkdwn,
+    /// Plain text without markup, e.g. for logs.
+    Plain,
+}
+
+/// Renders `blocks` as mrkdwn for a message's notification `text`.
+#[must_use]
+pub fn fallback_text(blocks: &[Block]) -> String {
+    render_text(blocks, TextFormat::Mrkdwn)
+}
+
+/// Renders `blocks` as text in the given format, one line per block.
+#[must_use]
+pub fn render_text(blocks: &[Block], format: TextFormat) -> String {
+    blocks
+        .iter()
+        .map(|block| render_block(block, format))
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_block(block: &Block, format: TextFormat) -> String {
+    match block {
+        Block::Header(header) => match format {
+            TextFormat::Mrkdwn => wrap(&escape(&header.text.text), "*"),
+            TextFormat::Plain => header.text.text.clone(),
+        },
+        Block::Section(section) => section
+            .text
+            .iter()
+            .chain(section.fields.iter().flatten())
+            .map(|text| render_text_object(text, format))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Block::Markdown(markdown) => markdown.text.clone(),
+        Block::Context(context) => context
+            .elements
+            .iter()
+            .filter_map(|element| render_element_text(element, format))
+            .collect::<Vec<_>>()
+            .join(" "),
+        Block::Video(video) => video.title.text.clone(),
+        Block::RichText(rich) => rich
+            .elements
+            .iter()
+            .map(|element| render_rich_element(element, format))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn render_text_object(text: &Text, format: TextFormat) -> String {
+    match (text, format) {
+        (Text::PlainText(plain), TextFormat::Mrkdwn) => escape(&plain.text),
+        (Text::PlainText(plain), TextFormat::Plain) => plain.text.clone(),
+        (Text::Mrkdwn(mrkdwn), TextFormat::Mrkdwn) => mrkdwn.text.clone(),
+        (Text::Mrkdwn(mrkdwn), TextFormat::Plain) => Document::parse(&mrkdwn.text).to_plain_text(),
+    }
+}
+
+/// Renders a text object used as a context element; images have no text.
+fn render_element_text(element: &Element, format: TextFormat) -> Option<String> {
+    match element {
+        Element::Text(text) => Some(render_text_object(text, format)),
+        _ => None,
+    }
+}
+
+fn render_rich_element(element: &RichTextElement, format: TextFormat) -> String {
+    match element {
+        RichTextElement::Section(section) => render_inlines(&section.elements, format),
+        RichTextElement::List(list) => {
+            let indent = "    ".repeat(list.indent.unwrap_or(0) as usize);
+            let offset = list.offset.unwrap_or(0) as usize;
+            list.elements
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    let bullet = match list.style {
+                        ListStyle::Bullet => "•".to_owned(),
+                        ListStyle::Ordered => format!("{}.", offset + i + 1),
+                    };
+                    format!(
+                        "{indent}{bullet} {}",
+                        render_inlines(&item.elements, format)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        RichTextElement::Preformatted(pre) => {
+            let code: String = pre
+                .elements
+                .iter()
+                .map(|el| render_inline(el, TextFormat::Plain))
+                .collect();
+            match format {
+                TextFormat::Mrkdwn => format!("```\n{code}\n```"),
+                TextFormat::Plain => code,
+            }
+        }
+        RichTextElement::Quote(quote) => {
+            let text = render_inlines(&quote.elements, format);
+            match format {
+                TextFormat::Mrkdwn => text
+                    .lines()
+                    .map(|line| format!("> {line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                TextFormat::Plain => text,
+            }
+        }
+    }
+}
+
+fn render_inlines(elements: &[RichTextInline], format: TextFormat) -> String {
+    elements
+        .iter()
+        .map(|el| render_inline(el, format))
+        .collect()
+}
+
+fn render_inline(element: &RichTextInline, format: TextFormat) -> String {
+    let mrkdwn = format == TextFormat::Mrkdwn;
+    match element {
+        RichTextInline::Text { text, style } if mrkdwn => styled(&escape(text), style.as_ref()),
+        RichTextInline::Text { text, .. } => text.clone(),
+        RichTextInline::Link { url, text, .. } => match (text, mrkdwn) {
+            (Some(text), true) => format!("<{url}|{}>", escape(text)),
+            (None, true) => format!("<{url}>"),
+            (Some(text), false) => text.clone(),
+            (None, false) => url.clone(),
+        },
+        RichTextInline::User { user_id, .. } if mrkdwn => format!("<@{user_id}>"),
+        RichTextInline::User { user_id, .. } => format!("@{user_id}"),
+        RichTextInline::Channel { channel_id, .. } if mrkdwn => format!("<#{channel_id}>"),
+        RichTextInline::Channel { channel_id, .. } => format!("#{channel_id}"),
+        RichTextInline::Usergroup { usergroup_id, .. } if mrkdwn => {
+            format!("<!subteam^{usergroup_id}>")
+        }
+        RichTextInline::Usergroup { usergroup_id, .. } => format!("@{usergroup_id}"),
+        RichTextInline::Broadcast { range } if mrkdwn => format!("<!{range}>"),
+        RichTextInline::Broadcast { range } => format!("@{range}"),
+        RichTextInline::Emoji { name, .. } => format!(":{name}:"),
+        RichTextInline::Date {
+            timestamp,
+            format: date_format,
+            fallback,
+            ..
+        } => {
+            let fallback = fallback.clone().unwrap_or_else(|| timestamp.to_string());
+            if mrkdwn {
+                format!("<!date^{timestamp}^{date_format}|{fallback}>")
+            } else {
+                fallback
+            }
+        }
+        RichTextInline::Color { value } => value.clone(),
+    }
+}
+
+/// Applies mrkdwn markers for `style`, innermost first.
+fn styled(text: &str, style: Option<&TextStyle>) -> String {
+    let Some(style) = style else {
+        return text.to_owned();
+    };
+    let mut out = text.to_owned();
+    for (set, marker) in [
+        (style.code, "`"),
+        (style.strike, "~"),
+        (style.italic, "_"),
+        (style.bold, "*"),
+    ] {
+        if set == Some(true) {
+            out = wrap(&out, marker);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{
+        Actions, Button, Context, Divider, Header, RichText, RichTextList, RichTextSection, Section,
+    };
+
+    fn blocks() -> Vec<Block> {
+        vec![
+            Header::new("Deploy <prod>").build(),
+            Section::new()
+                .text(Text::mrkdwn(
+                    "*Status:* done by <@U1|ana>, see <https://ci.example.com|CI>",
+                ))
+                .fields(vec![Text::plain("Region"), Text::mrkdwn("`eu_west`")])
+                .build(),
+            Divider::new().build(),
+            Actions::new(vec![Button::new("Retry").build()]).build(),
+            Context::new(vec![
+                Element::image("https://example.com/a.png", "avatar"),
+                Element::from(Text::mrkdwn("_2 minutes ago_")),
+            ])
+            .build(),
+            RichText::new(vec![
+                RichTextElement::Section(RichTextSection::new(vec![
+                    RichTextInline::styled(
+                        "Changes ",
+                        TextStyle {
+                            bold: Some(true),
+                            ..TextStyle::default()
+                        },
+                    ),
+                    RichTextInline::user("U2"),
+                ])),
+                RichTextElement::List(RichTextList::new(
+                    super::ListStyle::Ordered,
+                    vec![
+                        RichTextSection::new(vec![RichTextInline::text("api")]),
+                        RichTextSection::new(vec![RichTextInline::link(
+                            "https://example.com/w",
+                            Some("worker".to_owned()),
+                        )]),
+                    ],
+                )),
+            ])
+            .build(),
+        ]
+    }
+
+    #[test]
+    fn renders_mrkdwn_fallback() {
+        assert_eq!(
+            fallback_text(&blocks()),
+            "*Deploy &lt;prod&gt;*\n\
+             *Status:* done by <@U1|ana>, see <https://ci.example.com|CI>\n\
+             Region\n\
+             `eu_west`\n\
+             _2 minutes ago_\n\
+             *Changes* <@U2>\n\
+             1. api\n\
+             2. <https://example.com/w|worker>"
+        );
+    }
+
+    #[test]
+    fn renders_plain_text_without_markup() {
+        assert_eq!(
+            render_text(&blocks(), TextFormat::Plain),
+            "Deploy <prod>\n\
+             Status: done by @ana, see CI\n\
+             Region\n\
+             eu_west\n\
+             2 minutes ago\n\
+             Changes @U2\n\
+             1. api\n\
+             2. worker"
+        );
+    }
+
+    #[test]
+    fn blocks_without_text_render_empty() {
+        assert_eq!(fallback_text(&[Divider::new().build()]), "");
+    }
+}
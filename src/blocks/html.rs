@@ -0,0 +1,443 @@
+//! Sanitized HTML rendering of blocks and mrkdwn, e.g. for mirroring Slack
+//! messages into email or a web page.
+//!
+//! All text is escaped and only `http`, `https` and `mailto` links are kept,
+//! so the output is safe to embed even when message content is untrusted.
+//! Elements carry `slack-*` class names for styling.
+
+use std::fmt::Write;
+
+use super::{Block, Element, ListStyle, RichTextElement, RichTextInline, RichTextList, Text};
+use crate::mrkdwn::{Document, Node, Syntax, entity_text};
+
+/// Renders `blocks` as HTML, one element per block.
+#[must_use]
+pub fn render_html(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(render_block)
+        .filter(|html| !html.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders mrkdwn text as inline HTML, with `<br>` for line breaks and
+/// `<pre>`/`<blockquote>` for code blocks and quotes.
+#[must_use]
+pub fn mrkdwn_to_html(text: &str) -> String {
+    Document::parse(text).render(&Html)
+}
+
+/// HTML markup for parsed mrkdwn.
+struct Html;
+
+impl Syntax for Html {
+    fn text(&self, text: &str) -> String {
+        escape(text)
+    }
+
+    fn styled(&self, node: &Node, inner: String) -> String {
+        let tag = match node {
+            Node::Bold(_) => "strong",
+            Node::Italic(_) => "em",
+            _ => "s",
+        };
+        format!("<{tag}>{inner}</{tag}>")
+    }
+
+    fn code(&self, code: &str) -> String {
+        format!("<code>{}</code>", escape(code))
+    }
+
+    fn pre(&self, code: &str) -> String {
+        format!("<pre>{}</pre>", escape(code))
+    }
+
+    fn quote(&self, inner: &str) -> String {
+        format!("<blockquote>{inner}</blockquote>")
+    }
+
+    fn line_break(&self) -> &'static str {
+        "<br>"
+    }
+
+    fn block_gap(&self) -> &'static str {
+        ""
+    }
+
+    fn entity(&self, node: &Node) -> String {
+        let label = entity_text(node);
+        match node {
+            Node::Link { url, .. } => match safe_url(url) {
+                Some(url) => format!(r#"<a href="{}">{}</a>"#, escape(url), escape(&label)),
+                None => escape(&label),
+            },
+            Node::Date { .. } | Node::Emoji(_) => escape(&label),
+            _ => format!(r#"<span class="slack-mention">{}</span>"#, escape(&label)),
+        }
+    }
+}
+
+fn render_block(block: &Block) -> String {
+    match block {
+        Block::Header(header) => {
+            format!(
+                r#"<h3 class="slack-header">{}</h3>"#,
+                escape(&header.text.text)
+            )
+        }
+        Block::Section(section) => {
+            let mut out = String::from(r#"<div class="slack-section">"#);
+            if let Some(text) = &section.text {
+                let _ = write!(out, "<p>{}</p>", text_html(text));
+            }
+            if let Some(fields) = &section.fields {
+                out.push_str(r#"<div class="slack-fields">"#);
+                for field in fields {
+                    let _ = write!(
+                        out,
+                        r#"<div class="slack-field">{}</div>"#,
+                        text_html(field)
+                    );
+                }
+                out.push_str("</div>");
+            }
+            if let Some(accessory) = &section.accessory {
+                out.push_str(&element_html(accessory));
+            }
+            out.push_str("</div>");
+            out
+        }
+        Block::Divider(_) => "<hr>".to_owned(),
+        Block::Image(image) => {
+            let Some(src) = image.image_url.as_deref().and_then(safe_image_url) else {
+                return String::new();
+            };
+            let mut out = format!(
+                r#"<figure class="slack-image"><img src="{}" alt="{}">"#,
+                escape(src),
+                escape(&image.alt_text)
+            );
+            if let Some(title) = &image.title {
+                let _ = write!(out, "<figcaption>{}</figcaption>", escape(&title.text));
+            }
+            out.push_str("</figure>");
+            out
+        }
+        Block::Context(context) => {
+            let items: String = context.elements.iter().map(element_html).collect();
+            format!(r#"<div class="slack-context">{items}</div>"#)
+        }
+        Block::Actions(actions) => {
+            let items: String = actions.elements.iter().map(element_html).collect();
+            format!(r#"<div class="slack-actions">{items}</div>"#)
+        }
+        Block::Markdown(markdown) => markdown_html(&markdown.text),
+        Block::RichText(rich) => {
+            format!(
+                r#"<div class="slack-rich-text">{}</div>"#,
+                rich_text_html(&rich.elements)
+            )
+        }
+        Block::Video(video) => {
+            let url = video.title_url.as_deref().unwrap_or(&video.video_url);
+            let title = escape(&video.title.text);
+            match safe_url(url) {
+                Some(url) => format!(
+                    r#"<p class="slack-video"><a href="{}">{title}</a></p>"#,
+                    escape(url)
+                ),
+                None => format!(r#"<p class="slack-video">{title}</p>"#),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+#[cfg(feature = "markdown")]
+fn markdown_html(text: &str) -> String {
+    let rich = super::RichText::from_commonmark(text);
+    format!(
+        r#"<div class="slack-markdown">{}</div>"#,
+        rich_text_html(&rich.elements)
+    )
+}
+
+#[cfg(not(feature = "markdown"))]
+fn markdown_html(text: &str) -> String {
+    format!(
+        r#"<div class="slack-markdown"><p>{}</p></div>"#,
+        escape(text).replace('\n', "<br>")
+    )
+}
+
+fn text_html(text: &Text) -> String {
+    match text {
+        Text::PlainText(plain) => escape(&plain.text).replace('\n', "<br>"),
+        Text::Mrkdwn(mrkdwn) => mrkdwn_to_html(&mrkdwn.text),
+    }
+}
+
+/// Renders images, text objects and buttons; other elements are interactive
+/// only and are skipped.
+fn element_html(element: &Element) -> String {
+    match element {
+        Element::ImageElement(image) => match image.image_url.as_deref().and_then(safe_image_url) {
+            Some(src) => format!(
+                r#"<img class="slack-element-image" src="{}" alt="{}">"#,
+                escape(src),
+                escape(&image.alt_text)
+            ),
+            None => String::new(),
+        },
+        Element::Text(text) => format!("<span>{}</span>", text_html(text)),
+        Element::Button(button) => {
+            let label = &button.text.text;
+            match button.url.as_deref().and_then(safe_url) {
+                Some(url) => format!(
+                    r#"<a class="slack-button" href="{}">{}</a>"#,
+                    escape(url),
+                    escape(label)
+                ),
+                None => format!(r#"<span class="slack-button">{}</span>"#, escape(label)),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+/// An open list while rendering flattened rich text lists as nested HTML lists.
+struct OpenList {
+    tag: &'static str,
+    indent: u32,
+}
+
+fn rich_text_html(elements: &[RichTextElement]) -> String {
+    let mut out = String::new();
+    let mut lists: Vec<OpenList> = Vec::new();
+    for element in elements {
+        if let RichTextElement::List(list) = element {
+            list_html(&mut out, &mut lists, list);
+            continue;
+        }
+        close_lists(&mut out, &mut lists, 0);
+        match element {
+            RichTextElement::Section(section) => {
+                let _ = write!(out, "<p>{}</p>", inlines_html(&section.elements));
+            }
+            RichTextElement::Preformatted(pre) => {
+                let code: String = pre
+                    .elements
+                    .iter()
+                    .map(|el| match el {
+                        RichTextInline::Text { text, .. } => escape(text),
+                        other => inline_html(other),
+                    })
+                    .collect();
+                let _ = write!(out, "<pre>{code}</pre>");
+            }
+            RichTextElement::Quote(quote) => {
+                let _ = write!(
+                    out,
+                    "<blockquote>{}</blockquote>",
+                    inlines_html(&quote.elements)
+                );
+            }
+            RichTextElement::List(_) => {}
+        }
+    }
+    close_lists(&mut out, &mut lists, 0);
+    out
+}
+
+/// Appends the items of `list`, nesting it inside the open item of a list
+/// with a smaller indent.
+fn list_html(out: &mut String, lists: &mut Vec<OpenList>, list: &RichTextList) {
+    let indent = list.indent.unwrap_or(0);
+    let tag = match list.style {
+        ListStyle::Bullet => "ul",
+        ListStyle::Ordered => "ol",
+    };
+    // Close deeper lists, and a list at the same level with another style.
+    while let Some(top) = lists.last()
+        && (top.indent > indent || (top.indent == indent && top.tag != tag))
+    {
+        let top = lists.pop().expect("non-empty");
+        let _ = write!(out, "</li></{}>", top.tag);
+    }
+    if lists.last().is_some_and(|top| top.indent == indent) {
+        out.push_str("</li>");
+    } else {
+        match list.offset {
+            Some(offset) if tag == "ol" && offset > 0 => {
+                let _ = write!(out, r#"<ol start="{}">"#, offset + 1);
+            }
+            _ => {
+                let _ = write!(out, "<{tag}>");
+            }
+        }
+        lists.push(OpenList { tag, indent });
+    }
+    let items: Vec<String> = list
+        .elements
+        .iter()
+        .map(|item| format!("<li>{}", inlines_html(&item.elements)))
+        .collect();
+    out.push_str(&items.join("</li>"));
+}
+
+fn close_lists(out: &mut String, lists: &mut Vec<OpenList>, keep: usize) {
+    while lists.len() > keep {
+        let top = lists.pop().expect("non-empty");
+        let _ = write!(out, "</li></{}>", top.tag);
+    }
+}
+
+fn inlines_html(elements: &[RichTextInline]) -> String {
+    elements
+        .iter()
+        .map(inline_html)
+        .collect::<String>()
+        .replace('\n', "<br>")
+}
+
+fn inline_html(element: &RichTextInline) -> String {
+    let mention = |text: String| format!(r#"<span class="slack-mention">{}</span>"#, escape(&text));
+    match element {
+        RichTextInline::Text { text, style } => {
+            let mut html = escape(text);
+            if let Some(style) = style {
+                for (set, tag) in [
+                    (style.code, "code"),
+                    (style.strike, "s"),
+                    (style.italic, "em"),
+                    (style.bold, "strong"),
+                ] {
+                    if set == Some(true) {
+                        html = format!("<{tag}>{html}</{tag}>");
+                    }
+                }
+            }
+            html
+        }
+        RichTextInline::Link { url, text, .. } => {
+            let label = escape(text.as_deref().unwrap_or(url));
+            match safe_url(url) {
+                Some(url) => format!(r#"<a href="{}">{label}</a>"#, escape(url)),
+                None => label,
+            }
+        }
+        RichTextInline::User { user_id, .. } => mention(format!("@{user_id}")),
+        RichTextInline::Channel { channel_id, .. } => mention(format!("#{channel_id}")),
+        RichTextInline::Usergroup { usergroup_id, .. } => mention(format!("@{usergroup_id}")),
+        RichTextInline::Broadcast { range } => mention(format!("@{range}")),
+        RichTextInline::Emoji { name, unicode } => unicode
+            .as_deref()
+            .and_then(emoji_from_codepoints)
+            .unwrap_or_else(|| escape(&format!(":{name}:"))),
+        RichTextInline::Date {
+            timestamp,
+            fallback,
+            ..
+        } => escape(&fallback.clone().unwrap_or_else(|| timestamp.to_string())),
+        RichTextInline::Color { value } => {
+            format!(r#"<span class="slack-color">{}</span>"#, escape(value))
+        }
+    }
+}
+
+/// Decodes Slack's emoji `unicode` field, e.g. `1f44d-1f3fb`.
+fn emoji_from_codepoints(unicode: &str) -> Option<String> {
+    unicode
+        .split('-')
+        .map(|hex| u32::from_str_radix(hex, 16).ok().and_then(char::from_u32))
+        .collect()
+}
+
+/// Returns `url` if it uses a scheme that is safe to link to.
+fn safe_url(url: &str) -> Option<&str> {
+    let lower = url.trim_start().to_ascii_lowercase();
+    ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+        .then_some(url)
+}
+
+fn safe_image_url(url: &str) -> Option<&str> {
+    safe_url(url).filter(|url| !url.to_ascii_lowercase().starts_with("mailto:"))
+}
+
+/// Escapes text for use in HTML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mrkdwn_formatting_and_entities() {
+        assert_eq!(
+            mrkdwn_to_html("*Deploy* _now_ ~old~ `a<b` by <@U1|ana> in <#C1> <!here>"),
+            "<strong>Deploy</strong> <em>now</em> <s>old</s> <code>a&lt;b</code> by \
+             <span class=\"slack-mention\">@ana</span> in \
+             <span class=\"slack-mention\">#C1</span> \
+             <span class=\"slack-mention\">@here</span>"
+        );
+    }
+
+    #[test]
+    fn mrkdwn_keeps_markers_inside_words_and_breaks_lines() {
+        assert_eq!(
+            mrkdwn_to_html("snake_case_name\n2 * 3 = 6"),
+            "snake_case_name<br>2 * 3 = 6"
+        );
+    }
+
+    #[test]
+    fn mrkdwn_blocks_and_quotes() {
+        assert_eq!(
+            mrkdwn_to_html("Logs:\n```\nerr &lt;1&gt;\n```\n&gt; quoted\n&gt; *twice*"),
+            "Logs:<pre>err &lt;1&gt;</pre><blockquote>quoted<br><strong>twice</strong></blockquote>"
+        );
+    }
+
+    #[test]
+    fn mrkdwn_keeps_text_after_a_closing_fence() {
+        assert_eq!(
+            mrkdwn_to_html("```let x = 1;``` *done*\nnext"),
+            "<pre>let x = 1;</pre> <strong>done</strong><br>next"
+        );
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_blocks_render_through_rich_text() {
+        let block = crate::blocks::Markdown::new("**Notes:** see [docs](https://example.com/docs)");
+        assert_eq!(
+            render_html(&[block.build()]),
+            "<div class=\"slack-markdown\"><p><strong>Notes:</strong> see \
+             <a href=\"https://example.com/docs\">docs</a></p></div>"
+        );
+    }
+
+    #[test]
+    fn unsafe_links_and_markup_are_neutralized() {
+        assert_eq!(
+            mrkdwn_to_html("<javascript:alert(1)|click> <script>x</script> \"q\""),
+            "click scriptx/script &quot;q&quot;"
+        );
+    }
+}
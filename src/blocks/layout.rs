@@ -0,0 +1,269 @@
+//! Layout blocks.
+
+use super::{Element, PlainText, RichTextElement, SlackFile, Text};
+
+#[slaq_macros::block(kind = "divider")]
+#[derive(Debug, Clone)]
+/// Visually separates pieces of info inside of a message.
+pub struct Divider {
+    /// A unique identifier for a block. If not specified, one will be generated.
+    /// Maximum length for this field is 255 characters. `block_id`` should be unique
+    /// for each message and each iteration of a message. If a message is updated,
+    /// use a new `block_id`.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "markdown")]
+#[derive(Debug, Clone)]
+/// Displays formatted markdown.
+pub struct Markdown {
+    /// The standard markdown-formatted text. Limit 12,000 characters max, across all `markdown` blocks in a message.
+    pub text: String,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "section")]
+#[derive(Debug, Clone)]
+/// Displays text, possibly alongside fields and an accessory element.
+pub struct Section {
+    /// The text for the block. Maximum length is 3000 characters. One of `text`
+    /// or `fields` is required.
+    pub text: Option<Text>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+    /// Text objects rendered in a compact two-column layout. Maximum 10 items,
+    /// each up to 2000 characters.
+    pub fields: Option<Vec<Text>>,
+    /// An element shown next to the text, e.g. a button or an image.
+    pub accessory: Option<Element>,
+    /// Whether the text should always be shown in full instead of being truncated.
+    pub expand: Option<bool>,
+}
+
+#[slaq_macros::block(kind = "header")]
+#[derive(Debug, Clone)]
+/// Displays larger-sized text.
+pub struct Header {
+    /// The text for the block. Maximum length is 150 characters.
+    pub text: PlainText,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "context")]
+#[derive(Debug, Clone)]
+/// Displays contextual info as small images and text.
+pub struct Context {
+    /// Image elements and text objects. Maximum 10 items.
+    pub elements: Vec<Element>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "actions")]
+#[derive(Debug, Clone)]
+/// Holds multiple interactive elements.
+pub struct Actions {
+    /// Interactive elements. Maximum 25 items.
+    pub elements: Vec<Element>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "image")]
+#[derive(Debug, Clone)]
+/// Displays an image.
+pub struct Image {
+    /// A plain-text summary of the image. Maximum length is 2000 characters.
+    pub alt_text: String,
+    /// The URL of the image. One of `image_url` or `slack_file` is required.
+    pub image_url: Option<String>,
+    /// A Slack-hosted image. One of `image_url` or `slack_file` is required.
+    pub slack_file: Option<SlackFile>,
+    /// An optional title shown above the image. Maximum length is 2000 characters.
+    pub title: Option<PlainText>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "input")]
+#[derive(Debug, Clone)]
+/// Collects information from users, in modals and messages.
+pub struct Input {
+    /// A label shown above the element. Maximum length is 2000 characters.
+    pub label: PlainText,
+    /// The input element.
+    pub element: Element,
+    /// Whether the element dispatches a `block_actions` payload when used.
+    pub dispatch_action: Option<bool>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+    /// A hint shown below the element. Maximum length is 2000 characters.
+    pub hint: Option<PlainText>,
+    /// Whether the input may be empty when the form is submitted.
+    pub optional: Option<bool>,
+}
+
+#[slaq_macros::block(kind = "video")]
+#[derive(Debug, Clone)]
+/// Displays an embedded video player.
+pub struct Video {
+    /// A tooltip for the video.
+    pub alt_text: String,
+    /// Video title. Maximum length is 200 characters.
+    pub title: PlainText,
+    /// The thumbnail image URL.
+    pub thumbnail_url: String,
+    /// The URL of the embeddable video player.
+    pub video_url: String,
+    /// Author name shown in the video player. Maximum length is 50 characters.
+    pub author_name: Option<String>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+    /// Description of the video.
+    pub description: Option<PlainText>,
+    /// Icon of the video provider.
+    pub provider_icon_url: Option<String>,
+    /// The originating application or domain of the video.
+    pub provider_name: Option<String>,
+    /// Hyperlink for the title text.
+    pub title_url: Option<String>,
+}
+
+#[slaq_macros::block(kind = "file")]
+#[derive(Debug, Clone)]
+/// Displays info about a remote file.
+pub struct File {
+    /// The external unique ID of the file.
+    pub external_id: String,
+    /// Always `remote` for a remote file.
+    pub source: String,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[slaq_macros::block(kind = "rich_text")]
+#[derive(Debug, Clone)]
+/// Displays formatted, structured text.
+pub struct RichText {
+    /// Rich text sections, lists, preformatted blocks and quotes.
+    pub elements: Vec<RichTextElement>,
+    /// A unique identifier for a block. Maximum length is 255 characters.
+    pub block_id: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{Button, PlainTextInput, RichTextInline, RichTextSection};
+    use serde_json::json;
+
+    fn to_value(block: &crate::blocks::Block) -> serde_json::Value {
+        serde_json::to_value(block).unwrap()
+    }
+
+    #[test]
+    fn section_serializes_text_fields_and_accessory() {
+        let block = Section::new()
+            .text(Text::mrkdwn("*Deploy* finished"))
+            .fields(vec![Text::plain("Env"), Text::mrkdwn("`prod`")])
+            .accessory(Element::image("https://example.com/ok.png", "ok"))
+            .build();
+        assert_eq!(
+            to_value(&block),
+            json!({
+                "type": "section",
+                "text": {"type": "mrkdwn", "text": "*Deploy* finished"},
+                "fields": [
+                    {"type": "plain_text", "text": "Env"},
+                    {"type": "mrkdwn", "text": "`prod`"}
+                ],
+                "accessory": {"type": "image", "image_url": "https://example.com/ok.png", "alt_text": "ok"}
+            })
+        );
+    }
+
+    #[test]
+    fn header_and_context_serialize() {
+        let header = Header::new(PlainText::new("Release"))
+            .block_id("h1")
+            .build();
+        assert_eq!(
+            to_value(&header),
+            json!({"type": "header", "text": {"type": "plain_text", "text": "Release"}, "block_id": "h1"})
+        );
+
+        let context = Context::new(vec![
+            Element::from(Text::mrkdwn("by <@U123>")),
+            Element::image("https://example.com/a.png", "avatar"),
+        ])
+        .build();
+        let value = to_value(&context);
+        assert_eq!(value["type"], "context");
+        assert_eq!(value["elements"][0]["type"], "mrkdwn");
+        assert_eq!(value["elements"][1]["type"], "image");
+    }
+
+    #[test]
+    fn image_input_video_file_and_rich_text_serialize() {
+        let image = Image::new("chart")
+            .image_url("https://example.com/c.png")
+            .title(PlainText::new("Chart"))
+            .build();
+        assert_eq!(to_value(&image)["image_url"], "https://example.com/c.png");
+
+        let input = Input::new(
+            PlainText::new("Reason"),
+            PlainTextInput::new().action_id("reason").build(),
+        )
+        .optional(true)
+        .build();
+        let value = to_value(&input);
+        assert_eq!(value["label"]["text"], "Reason");
+        assert_eq!(value["element"]["action_id"], "reason");
+        assert_eq!(value["optional"], true);
+
+        let video = Video::new(
+            "demo",
+            PlainText::new("Demo"),
+            "https://example.com/t.png",
+            "https://example.com/embed",
+        )
+        .build();
+        assert_eq!(to_value(&video)["video_url"], "https://example.com/embed");
+
+        let file = File::new("ABCD1", "remote").build();
+        assert_eq!(
+            to_value(&file),
+            json!({"type": "file", "external_id": "ABCD1", "source": "remote"})
+        );
+
+        let rich = RichText::new(vec![RichTextElement::Section(RichTextSection::new(vec![
+            RichTextInline::text("hi"),
+        ]))])
+        .build();
+        assert_eq!(
+            to_value(&rich),
+            json!({
+                "type": "rich_text",
+                "elements": [{
+                    "type": "rich_text_section",
+                    "elements": [{"type": "text", "text": "hi"}]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn actions_composes_with_post_message() {
+        let blocks = vec![
+            Actions::new(vec![Button::new("A").action_id("a").build()]).build(),
+            Divider::new().build(),
+        ];
+        let msg = crate::api::chat::post_message::PostMessage::new("C1").blocks(blocks);
+        let value = serde_json::to_value(&msg).unwrap();
+        assert_eq!(value["blocks"][0]["type"], "actions");
+        assert_eq!(value["blocks"][0]["elements"][0]["action_id"], "a");
+    }
+}
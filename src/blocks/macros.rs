@@ -0,0 +1,264 @@
+//! The `blocks!` macro for composing block lists inline.
+//!
+//! The traits here convert the macro's arguments and are not meant to be
+//! used directly.
+
+use super::{Element, MrkdwnText, PlainText, Text};
+use crate::mrkdwn::Mrkdwn;
+
+/// Builds a `Vec<Block>` from a list of blocks, conditionals and loops.
+///
+/// Items are separated by commas. The shorthands take the block's required
+/// arguments and may be followed by setters, which are applied before
+/// `build()`:
+///
+/// - `header(text)`, `divider`, `markdown(text)`
+/// - `section(text)`, `section(text, fields: [..])`, `section(fields: [..])`
+/// - `context(elements..)`, `actions(elements..)`
+/// - `if (condition) { .. } else { .. }`, with the `else` optional
+/// - `for pattern in (iterator) { .. }`
+/// - any other expression, which must be a `Block`
+///
+/// Strings given as section text, fields or context elements are mrkdwn;
+/// `Text`, `Mrkdwn` and `Element` values are used as they are.
+///
+/// ```
+/// use slaq::blocks::{Button, Image};
+///
+/// let failed = ["api", "worker"];
+/// let blocks = slaq::blocks![
+///     header("Deploy finished"),
+///     section("*Status:* partial", fields: ["*Env*", "prod"]).block_id("status"),
+///     divider,
+///     for name in (failed) {
+///         section(format!("`{name}` failed")),
+///     }
+///     if (failed.is_empty()) {
+///         markdown("All services are up."),
+///     } else {
+///         actions(Button::new("Retry").action_id("retry").build()),
+///     }
+///     context("Triggered by <@U123>"),
+///     Image::new("Build graph").image_url("https://example.com/graph.png").build(),
+/// ];
+/// assert_eq!(blocks.len(), 8);
+/// ```
+#[macro_export]
+macro_rules! blocks {
+    ($($body:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut blocks = ::std::vec::Vec::<$crate::blocks::Block>::new();
+        $crate::__blocks!(@push blocks; $($body)*);
+        blocks
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __blocks {
+    (@push $out:ident;) => {};
+    // A comma after an `if` or `for` body.
+    (@push $out:ident; , $($rest:tt)*) => {
+        $crate::__blocks!(@push $out; $($rest)*);
+    };
+    (@push $out:ident; header($text:expr $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out, $crate::blocks::Header::new($text)$(.$setter($($arg),*))*.build());
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; divider $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out, $crate::blocks::Divider::new()$(.$setter($($arg),*))*.build());
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; markdown($text:expr $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out, $crate::blocks::Markdown::new($text)$(.$setter($($arg),*))*.build());
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; section(fields: [$($field:expr),* $(,)?] $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out,
+            $crate::blocks::Section::new()
+                .fields(::std::vec![$($crate::blocks::macros::BlockText::into_text($field)),*])
+                $(.$setter($($arg),*))*
+                .build(),
+        );
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; section($text:expr, fields: [$($field:expr),* $(,)?] $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out,
+            $crate::blocks::Section::new()
+                .text($crate::blocks::macros::BlockText::into_text($text))
+                .fields(::std::vec![$($crate::blocks::macros::BlockText::into_text($field)),*])
+                $(.$setter($($arg),*))*
+                .build(),
+        );
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; section($text:expr $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out,
+            $crate::blocks::Section::new()
+                .text($crate::blocks::macros::BlockText::into_text($text))
+                $(.$setter($($arg),*))*
+                .build(),
+        );
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; context($($element:expr),* $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out,
+            $crate::blocks::Context::new(::std::vec![
+                $($crate::blocks::macros::BlockElement::into_element($element)),*
+            ])
+            $(.$setter($($arg),*))*
+            .build(),
+        );
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; actions($($element:expr),* $(,)?) $(.$setter:ident($($arg:expr),* $(,)?))* $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out,
+            $crate::blocks::Actions::new(::std::vec![
+                $($crate::blocks::macros::BlockElement::into_element($element)),*
+            ])
+            $(.$setter($($arg),*))*
+            .build(),
+        );
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+    (@push $out:ident; if ($cond:expr) { $($then:tt)* } else { $($otherwise:tt)* } $($rest:tt)*) => {
+        if $cond {
+            $crate::__blocks!(@push $out; $($then)*);
+        } else {
+            $crate::__blocks!(@push $out; $($otherwise)*);
+        }
+        $crate::__blocks!(@push $out; $($rest)*);
+    };
+    (@push $out:ident; if ($cond:expr) { $($then:tt)* } $($rest:tt)*) => {
+        if $cond {
+            $crate::__blocks!(@push $out; $($then)*);
+        }
+        $crate::__blocks!(@push $out; $($rest)*);
+    };
+    (@push $out:ident; for $pat:pat in ($iter:expr) { $($body:tt)* } $($rest:tt)*) => {
+        for $pat in $iter {
+            $crate::__blocks!(@push $out; $($body)*);
+        }
+        $crate::__blocks!(@push $out; $($rest)*);
+    };
+    (@push $out:ident; $block:expr $(, $($rest:tt)*)?) => {
+        ::std::vec::Vec::push(&mut $out, $block);
+        $crate::__blocks!(@push $out; $($($rest)*)?);
+    };
+}
+
+/// Text accepted by `blocks!`: strings are mrkdwn.
+pub trait BlockText {
+    fn into_text(self) -> Text;
+}
+
+impl BlockText for &str {
+    fn into_text(self) -> Text {
+        Text::mrkdwn(self)
+    }
+}
+
+impl BlockText for String {
+    fn into_text(self) -> Text {
+        Text::mrkdwn(self)
+    }
+}
+
+impl BlockText for &String {
+    fn into_text(self) -> Text {
+        Text::mrkdwn(self.as_str())
+    }
+}
+
+impl BlockText for Text {
+    fn into_text(self) -> Text {
+        self
+    }
+}
+
+impl BlockText for PlainText {
+    fn into_text(self) -> Text {
+        self.into()
+    }
+}
+
+impl BlockText for MrkdwnText {
+    fn into_text(self) -> Text {
+        self.into()
+    }
+}
+
+impl BlockText for Mrkdwn {
+    fn into_text(self) -> Text {
+        self.into()
+    }
+}
+
+/// Elements accepted by `blocks!`: text is converted like [`BlockText`].
+pub trait BlockElement {
+    fn into_element(self) -> Element;
+}
+
+impl<T: BlockText> BlockElement for T {
+    fn into_element(self) -> Element {
+        self.into_text().into()
+    }
+}
+
+impl BlockElement for Element {
+    fn into_element(self) -> Element {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::blocks::{Block, Button, Divider, Element, Text};
+
+    #[test]
+    fn shorthands_match_builders() {
+        let expected = vec![
+            crate::blocks::Header::new("Title").build(),
+            Divider::new().block_id("d").build(),
+            crate::blocks::Section::new()
+                .text(Text::mrkdwn("*a*"))
+                .fields(vec![Text::mrkdwn("b"), Text::plain("c")])
+                .build(),
+            crate::blocks::Context::new(vec![
+                Element::image("https://x.test/a.png", "a"),
+                Element::from(Text::mrkdwn("d")),
+            ])
+            .build(),
+        ];
+        let blocks = crate::blocks![
+            header("Title"),
+            divider.block_id("d"),
+            section("*a*", fields: ["b", Text::plain("c")]),
+            context(Element::image("https://x.test/a.png", "a"), "d"),
+        ];
+        let json = |blocks: &[Block]| serde_json::to_value(blocks).unwrap();
+        assert_eq!(json(&blocks), json(&expected));
+    }
+
+    #[test]
+    fn conditionals_and_loops_expand_in_place() {
+        let items = ["x", "y"];
+        let show = false;
+        let blocks = crate::blocks![
+            for item in (items) {
+                section(item),
+            }
+            if (show) { divider } else { actions(Button::new("Go").build()) }
+            Divider::new().build()
+        ];
+        let kinds: Vec<&str> = blocks.iter().map(Block::kind).collect();
+        assert_eq!(kinds, ["section", "section", "actions", "divider"]);
+        assert_eq!(
+            serde_json::to_value(&blocks[1]).unwrap()["text"],
+            json!({"type": "mrkdwn", "text": "y"})
+        );
+        assert!(crate::blocks![].is_empty());
+    }
+}
@@ -1,36 +1,375 @@
-//! Minimal BlockKit-like builders for composing message blocks.
+//! `BlockKit` builders for composing message blocks.
 //!
-//! This module currently supports two simple block variants:
-//! - Divider: `{ "type": "divider", "block_id": "..."? }`
-//! - Markdown: `{ "type": "markdown", "text": "...", "block_id": "..."? }`
+//! Layout blocks are built with `new(..)`, optional setters and `build()`,
+//! which produces a `Block` ready for `PostMessage::blocks` and friends. `Block`
+//! also deserializes, so blocks of received messages can be edited and re-sent:
+//! - `Divider`, `Markdown`, `Section`, `Header`, `Context`, `Actions`
+//! - `Image`, `Input`, `Video`, `File`, `RichText`
 //!
-//! These are intentionally simplified and not a full Block Kit implementation.
+//! The `blocks!` macro composes a `Vec<Block>` inline, with shorthands for
+//! common blocks, conditionals and loops.
+//!
+//! `RichText` holds a typed element tree (`RichTextElement`, `RichTextInline`).
+//! With the `markdown` feature, `RichText::from_commonmark` builds one from
+//! `CommonMark` input.
+//!
+//! Interactive elements (buttons, selects, pickers, inputs, ...) follow the same
+//! pattern, with `build()` producing an `Element` for `actions` blocks, section
+//! accessories and `input` blocks. `Element` deserializes like `Block`.
+//!
+//! Blocks are composed from elements and composition objects:
+//! text objects (`Text`, `PlainText`, `MrkdwnText`), options, option groups,
+//! confirmation dialogs, conversation filters, dispatch action configs and
+//! workflow triggers.
+//!
+//! `validate` checks blocks against Slack's documented limits before sending,
+//! reporting the path of each offending field. `fallback_text` summarizes
+//! blocks as mrkdwn for a message's notification `text`, and `render_html`
+//! renders them as sanitized HTML.
 
-use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json as json;
 
-/// A minimal set of supported blocks.
-#[derive(Debug, Clone, Serialize)]
-#[serde(transparent)]
-pub struct Block(pub json::Value);
+#[cfg(feature = "markdown")]
+mod commonmark;
+mod composition;
+mod elements;
+mod fallback;
+mod html;
+mod layout;
+#[doc(hidden)]
+pub mod macros;
+mod rich_text;
+mod validate;
+
+pub use composition::{
+    ButtonStyle, ConfirmationDialog, ConversationFilter, ConversationType, DispatchActionConfig,
+    InputParameter, MrkdwnText, OptionGroup, OptionObject, PlainText, SlackFile, Text, Trigger,
+    TriggerAction, Workflow,
+};
+pub use elements::{
+    Button, ChannelsSelect, Checkboxes, ConversationsSelect, DatePicker, DatetimePicker,
+    EmailInput, ExternalSelect, FileInput, ImageElement, MultiChannelsSelect,
+    MultiConversationsSelect, MultiExternalSelect, MultiStaticSelect, MultiUsersSelect,
+    NumberInput, Overflow, PlainTextInput, RadioButtons, StaticSelect, TimePicker, UrlInput,
+    UsersSelect, WorkflowButton,
+};
+pub use fallback::{TextFormat, fallback_text, render_text};
+pub use html::{mrkdwn_to_html, render_html};
+pub use layout::{
+    Actions, Context, Divider, File, Header, Image, Input, Markdown, RichText, Section, Video,
+};
+pub use rich_text::{
+    ListStyle, RichTextElement, RichTextInline, RichTextList, RichTextPreformatted, RichTextQuote,
+    RichTextSection, TextStyle,
+};
+pub use validate::{BlockError, BlockErrorKind, BlockErrors, Surface, validate, validate_for};
 
-#[slaq_macros::block(kind = "divider")]
+/// A layout block.
+///
+/// Blocks deserialize into the variant matching their `type`, so messages read
+/// back from Slack can be inspected, modified and sent again. Blocks of types
+/// this crate does not model, or with fields it does not know yet, deserialize
+/// to `Unknown` with their raw JSON, so re-posting them never drops data.
 #[derive(Debug, Clone)]
-/// Visually separates pieces of info inside of a message.
-pub struct Divider {
-    /// A unique identifier for a block. If not specified, one will be generated.
-    /// Maximum length for this field is 255 characters. `block_id`` should be unique
-    /// for each message and each iteration of a message. If a message is updated,
-    /// use a new `block_id`.
-    pub block_id: Option<String>,
+pub enum Block {
+    Actions(Actions),
+    Context(Context),
+    Divider(Divider),
+    File(File),
+    Header(Header),
+    Image(Image),
+    Input(Input),
+    Markdown(Markdown),
+    RichText(RichText),
+    Section(Section),
+    Video(Video),
+    /// A block kept as raw JSON.
+    Unknown(json::Value),
+}
+
+impl Block {
+    /// The block's `type`, e.g. `section`.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Block::Actions(_) => "actions",
+            Block::Context(_) => "context",
+            Block::Divider(_) => "divider",
+            Block::File(_) => "file",
+            Block::Header(_) => "header",
+            Block::Image(_) => "image",
+            Block::Input(_) => "input",
+            Block::Markdown(_) => "markdown",
+            Block::RichText(_) => "rich_text",
+            Block::Section(_) => "section",
+            Block::Video(_) => "video",
+            Block::Unknown(value) => value
+                .get("type")
+                .and_then(json::Value::as_str)
+                .unwrap_or(""),
+        }
+    }
+
+    /// The block's `block_id`, if set.
+    #[must_use]
+    pub fn block_id(&self) -> Option<&str> {
+        match self {
+            Block::Actions(block) => block.block_id.as_deref(),
+            Block::Context(block) => block.block_id.as_deref(),
+            Block::Divider(block) => block.block_id.as_deref(),
+            Block::File(block) => block.block_id.as_deref(),
+            Block::Header(block) => block.block_id.as_deref(),
+            Block::Image(block) => block.block_id.as_deref(),
+            Block::Input(block) => block.block_id.as_deref(),
+            Block::Markdown(block) => block.block_id.as_deref(),
+            Block::RichText(block) => block.block_id.as_deref(),
+            Block::Section(block) => block.block_id.as_deref(),
+            Block::Video(block) => block.block_id.as_deref(),
+            Block::Unknown(value) => value.get("block_id").and_then(json::Value::as_str),
+        }
+    }
+}
+
+// Each block struct carries its own `type` tag, so serialization delegates to it.
+impl Serialize for Block {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Block::Actions(block) => block.serialize(serializer),
+            Block::Context(block) => block.serialize(serializer),
+            Block::Divider(block) => block.serialize(serializer),
+            Block::File(block) => block.serialize(serializer),
+            Block::Header(block) => block.serialize(serializer),
+            Block::Image(block) => block.serialize(serializer),
+            Block::Input(block) => block.serialize(serializer),
+            Block::Markdown(block) => block.serialize(serializer),
+            Block::RichText(block) => block.serialize(serializer),
+            Block::Section(block) => block.serialize(serializer),
+            Block::Video(block) => block.serialize(serializer),
+            Block::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Block {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = json::Value::deserialize(deserializer)?;
+        let block = match value.get("type").and_then(json::Value::as_str) {
+            Some("actions") => typed(&value, Block::Actions),
+            Some("context") => typed(&value, Block::Context),
+            Some("divider") => typed(&value, Block::Divider),
+            Some("file") => typed(&value, Block::File),
+            Some("header") => typed(&value, Block::Header),
+            Some("image") => typed(&value, Block::Image),
+            Some("input") => typed(&value, Block::Input),
+            Some("markdown") => typed(&value, Block::Markdown),
+            Some("rich_text") => typed(&value, Block::RichText),
+            Some("section") => typed(&value, Block::Section),
+            Some("video") => typed(&value, Block::Video),
+            _ => None,
+        };
+        Ok(block.unwrap_or(Block::Unknown(value)))
+    }
 }
 
-#[slaq_macros::block(kind = "markdown")]
+/// Decodes `value` as `T`, unless that would lose any of its fields.
+fn typed<T, U>(value: &json::Value, variant: fn(T) -> U) -> Option<U>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let decoded: T = json::from_value(value.clone()).ok()?;
+    (json::to_value(&decoded).ok()? == *value).then(|| variant(decoded))
+}
+
+/// An element placed inside a block, e.g. a section accessory, an entry of
+/// an `actions` or `context` block, or the element of an `input` block.
+///
+/// Like [`Block`], elements deserialize into the variant matching their
+/// `type`; text objects in `context` blocks become `Text`, and elements this
+/// crate does not model, or with unknown fields, are kept as raw JSON.
 #[derive(Debug, Clone)]
-/// Displays formatted markdown.
-pub struct Markdown {
-    /// The standard markdown-formatted text. Limit 12,000 characters max.
-    pub text: String,
+pub enum Element {
+    Button(Button),
+    WorkflowButton(WorkflowButton),
+    StaticSelect(StaticSelect),
+    MultiStaticSelect(MultiStaticSelect),
+    ExternalSelect(ExternalSelect),
+    MultiExternalSelect(MultiExternalSelect),
+    UsersSelect(UsersSelect),
+    MultiUsersSelect(MultiUsersSelect),
+    ConversationsSelect(ConversationsSelect),
+    MultiConversationsSelect(MultiConversationsSelect),
+    ChannelsSelect(ChannelsSelect),
+    MultiChannelsSelect(MultiChannelsSelect),
+    Overflow(Overflow),
+    Checkboxes(Checkboxes),
+    RadioButtons(RadioButtons),
+    DatePicker(DatePicker),
+    TimePicker(TimePicker),
+    DatetimePicker(DatetimePicker),
+    PlainTextInput(PlainTextInput),
+    EmailInput(EmailInput),
+    UrlInput(UrlInput),
+    NumberInput(NumberInput),
+    FileInput(FileInput),
+    ImageElement(ImageElement),
+    /// A text object, as used in `context` blocks.
+    Text(Text),
+    /// An element kept as raw JSON.
+    Unknown(json::Value),
+}
+
+impl Element {
+    /// Creates an image element, as used in `context` blocks and section accessories.
+    #[must_use]
+    pub fn image(image_url: impl Into<String>, alt_text: impl Into<String>) -> Self {
+        ImageElement::new(alt_text).image_url(image_url).build()
+    }
+
+    /// The element's `type`, e.g. `button`.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Element::Button(_) => "button",
+            Element::WorkflowButton(_) => "workflow_button",
+            Element::StaticSelect(_) => "static_select",
+            Element::MultiStaticSelect(_) => "multi_static_select",
+            Element::ExternalSelect(_) => "external_select",
+            Element::MultiExternalSelect(_) => "multi_external_select",
+            Element::UsersSelect(_) => "users_select",
+            Element::MultiUsersSelect(_) => "multi_users_select",
+            Element::ConversationsSelect(_) => "conversations_select",
+            Element::MultiConversationsSelect(_) => "multi_conversations_select",
+            Element::ChannelsSelect(_) => "channels_select",
+            Element::MultiChannelsSelect(_) => "multi_channels_select",
+            Element::Overflow(_) => "overflow",
+            Element::Checkboxes(_) => "checkboxes",
+            Element::RadioButtons(_) => "radio_buttons",
+            Element::DatePicker(_) => "datepicker",
+            Element::TimePicker(_) => "timepicker",
+            Element::DatetimePicker(_) => "datetimepicker",
+            Element::PlainTextInput(_) => "plain_text_input",
+            Element::EmailInput(_) => "email_text_input",
+            Element::UrlInput(_) => "url_text_input",
+            Element::NumberInput(_) => "number_input",
+            Element::FileInput(_) => "file_input",
+            Element::ImageElement(_) => "image",
+            Element::Text(Text::PlainText(_)) => "plain_text",
+            Element::Text(Text::Mrkdwn(_)) => "mrkdwn",
+            Element::Unknown(value) => value
+                .get("type")
+                .and_then(json::Value::as_str)
+                .unwrap_or(""),
+        }
+    }
+
+    /// The element's `action_id`, if set. Images and text objects have none.
+    #[must_use]
+    pub fn action_id(&self) -> Option<&str> {
+        match self {
+            Element::Button(element) => element.action_id.as_deref(),
+            Element::WorkflowButton(element) => element.action_id.as_deref(),
+            Element::StaticSelect(element) => element.action_id.as_deref(),
+            Element::MultiStaticSelect(element) => element.action_id.as_deref(),
+            Element::ExternalSelect(element) => element.action_id.as_deref(),
+            Element::MultiExternalSelect(element) => element.action_id.as_deref(),
+            Element::UsersSelect(element) => element.action_id.as_deref(),
+            Element::MultiUsersSelect(element) => element.action_id.as_deref(),
+            Element::ConversationsSelect(element) => element.action_id.as_deref(),
+            Element::MultiConversationsSelect(element) => element.action_id.as_deref(),
+            Element::ChannelsSelect(element) => element.action_id.as_deref(),
+            Element::MultiChannelsSelect(element) => element.action_id.as_deref(),
+            Element::Overflow(element) => element.action_id.as_deref(),
+            Element::Checkboxes(element) => element.action_id.as_deref(),
+            Element::RadioButtons(element) => element.action_id.as_deref(),
+            Element::DatePicker(element) => element.action_id.as_deref(),
+            Element::TimePicker(element) => element.action_id.as_deref(),
+            Element::DatetimePicker(element) => element.action_id.as_deref(),
+            Element::PlainTextInput(element) => element.action_id.as_deref(),
+            Element::EmailInput(element) => element.action_id.as_deref(),
+            Element::UrlInput(element) => element.action_id.as_deref(),
+            Element::NumberInput(element) => element.action_id.as_deref(),
+            Element::FileInput(element) => element.action_id.as_deref(),
+            Element::ImageElement(_) | Element::Text(_) => None,
+            Element::Unknown(value) => value.get("action_id").and_then(json::Value::as_str),
+        }
+    }
+}
+
+impl From<Text> for Element {
+    fn from(text: Text) -> Self {
+        Self::Text(text)
+    }
+}
+
+// Each element carries its own `type` tag, so serialization delegates to it.
+impl Serialize for Element {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Element::Button(element) => element.serialize(serializer),
+            Element::WorkflowButton(element) => element.serialize(serializer),
+            Element::StaticSelect(element) => element.serialize(serializer),
+            Element::MultiStaticSelect(element) => element.serialize(serializer),
+            Element::ExternalSelect(element) => element.serialize(serializer),
+            Element::MultiExternalSelect(element) => element.serialize(serializer),
+            Element::UsersSelect(element) => element.serialize(serializer),
+            Element::MultiUsersSelect(element) => element.serialize(serializer),
+            Element::ConversationsSelect(element) => element.serialize(serializer),
+            Element::MultiConversationsSelect(element) => element.serialize(serializer),
+            Element::ChannelsSelect(element) => element.serialize(serializer),
+            Element::MultiChannelsSelect(element) => element.serialize(serializer),
+            Element::Overflow(element) => element.serialize(serializer),
+            Element::Checkboxes(element) => element.serialize(serializer),
+            Element::RadioButtons(element) => element.serialize(serializer),
+            Element::DatePicker(element) => element.serialize(serializer),
+            Element::TimePicker(element) => element.serialize(serializer),
+            Element::DatetimePicker(element) => element.serialize(serializer),
+            Element::PlainTextInput(element) => element.serialize(serializer),
+            Element::EmailInput(element) => element.serialize(serializer),
+            Element::UrlInput(element) => element.serialize(serializer),
+            Element::NumberInput(element) => element.serialize(serializer),
+            Element::FileInput(element) => element.serialize(serializer),
+            Element::ImageElement(element) => element.serialize(serializer),
+            Element::Text(text) => text.serialize(serializer),
+            Element::Unknown(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Element {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = json::Value::deserialize(deserializer)?;
+        let element = match value.get("type").and_then(json::Value::as_str) {
+            Some("button") => typed(&value, Element::Button),
+            Some("workflow_button") => typed(&value, Element::WorkflowButton),
+            Some("static_select") => typed(&value, Element::StaticSelect),
+            Some("multi_static_select") => typed(&value, Element::MultiStaticSelect),
+            Some("external_select") => typed(&value, Element::ExternalSelect),
+            Some("multi_external_select") => typed(&value, Element::MultiExternalSelect),
+            Some("users_select") => typed(&value, Element::UsersSelect),
+            Some("multi_users_select") => typed(&value, Element::MultiUsersSelect),
+            Some("conversations_select") => typed(&value, Element::ConversationsSelect),
+            Some("multi_conversations_select") => typed(&value, Element::MultiConversationsSelect),
+            Some("channels_select") => typed(&value, Element::ChannelsSelect),
+            Some("multi_channels_select") => typed(&value, Element::MultiChannelsSelect),
+            Some("overflow") => typed(&value, Element::Overflow),
+            Some("checkboxes") => typed(&value, Element::Checkboxes),
+            Some("radio_buttons") => typed(&value, Element::RadioButtons),
+            Some("datepicker") => typed(&value, Element::DatePicker),
+            Some("timepicker") => typed(&value, Element::TimePicker),
+            Some("datetimepicker") => typed(&value, Element::DatetimePicker),
+            Some("plain_text_input") => typed(&value, Element::PlainTextInput),
+            Some("email_text_input") => typed(&value, Element::EmailInput),
+            Some("url_text_input") => typed(&value, Element::UrlInput),
+            Some("number_input") => typed(&value, Element::NumberInput),
+            Some("file_input") => typed(&value, Element::FileInput),
+            Some("image") => typed(&value, Element::ImageElement),
+            Some("plain_text" | "mrkdwn") => typed(&value, Element::Text),
+            _ => None,
+        };
+        Ok(element.unwrap_or(Element::Unknown(value)))
+    }
 }
 
 #[cfg(test)]
@@ -51,4 +390,63 @@ mod tests {
         assert!(json.contains("\"type\":\"markdown\""));
         assert!(json.contains("\"text\":\"hello\""));
     }
+
+    #[test]
+    fn blocks_deserialize_into_typed_variants() {
+        let raw = json::json!([
+            {"type": "header", "block_id": "h", "text": {"type": "plain_text", "text": "Hi", "emoji": true}},
+            {"type": "section", "text": {"type": "mrkdwn", "text": "*bold*", "verbatim": false},
+             "accessory": {"type": "button", "text": {"type": "plain_text", "text": "Go"}, "action_id": "go"}},
+            {"type": "rich_text", "elements": [
+                {"type": "rich_text_section", "elements": [{"type": "user", "user_id": "U1"}]}
+            ]},
+            {"type": "divider"}
+        ]);
+        let blocks: Vec<Block> = json::from_value(raw.clone()).unwrap();
+        let Block::Section(section) = &blocks[1] else {
+            panic!("expected a section, got {:?}", blocks[1]);
+        };
+        let Some(Element::Button(button)) = &section.accessory else {
+            panic!("expected a button, got {:?}", section.accessory);
+        };
+        assert_eq!(button.action_id.as_deref(), Some("go"));
+        assert!(matches!(&blocks[0], Block::Header(h) if h.block_id.as_deref() == Some("h")));
+        assert!(matches!(blocks[2], Block::RichText(_)));
+        assert_eq!(blocks[3].kind(), "divider");
+        assert_eq!(json::to_value(&blocks).unwrap(), raw);
+    }
+
+    #[test]
+    fn unknown_blocks_and_fields_round_trip_raw() {
+        let raw = json::json!([
+            {"type": "table", "rows": [[{"type": "raw_text", "text": "a"}]]},
+            {"type": "divider", "block_id": "d", "new_field": 1}
+        ]);
+        let blocks: Vec<Block> = json::from_value(raw.clone()).unwrap();
+        assert!(matches!(blocks[0], Block::Unknown(_)));
+        assert_eq!(blocks[0].kind(), "table");
+        assert!(matches!(blocks[1], Block::Unknown(_)));
+        assert_eq!(json::to_value(&blocks).unwrap(), raw);
+    }
+
+    #[test]
+    fn elements_deserialize_into_typed_variants() {
+        let raw = json::json!([
+            {"type": "image", "image_url": "https://x.test/a.png", "alt_text": "a"},
+            {"type": "mrkdwn", "text": "*hi*"},
+            {"type": "static_select", "action_id": "s", "options": [
+                {"text": {"type": "plain_text", "text": "A"}, "value": "a"}
+            ]},
+            {"type": "rich_text_input", "action_id": "r"},
+            {"type": "button", "text": {"type": "plain_text", "text": "Go"}, "new_field": 1}
+        ]);
+        let elements: Vec<Element> = json::from_value(raw.clone()).unwrap();
+        assert!(matches!(&elements[0], Element::ImageElement(i) if i.alt_text == "a"));
+        assert!(matches!(elements[1], Element::Text(Text::Mrkdwn(_))));
+        assert!(matches!(elements[2], Element::StaticSelect(_)));
+        assert!(matches!(elements[3], Element::Unknown(_)));
+        assert_eq!(elements[3].kind(), "rich_text_input");
+        assert!(matches!(elements[4], Element::Unknown(_)));
+        assert_eq!(json::to_value(&elements).unwrap(), raw);
+    }
 }
@@ -0,0 +1,300 @@
+//! Rich text element tree used by the `rich_text` block.
+//!
+//! A `RichText` block holds top-level sections, lists, preformatted blocks
+//! and quotes, which in turn hold styled inline elements.
+
+use serde::{Deserialize, Serialize, Serializer};
+use serde_with::skip_serializing_none;
+
+use crate::id::{ChannelId, UserId};
+
+/// A top-level element of a `rich_text` block.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type")]
+pub enum RichTextElement {
+    /// A paragraph of inline elements.
+    #[serde(rename = "rich_text_section")]
+    Section(RichTextSection),
+    /// A bulleted or ordered list.
+    #[serde(rename = "rich_text_list")]
+    List(RichTextList),
+    /// A code block.
+    #[serde(rename = "rich_text_preformatted")]
+    Preformatted(RichTextPreformatted),
+    /// A block quote.
+    #[serde(rename = "rich_text_quote")]
+    Quote(RichTextQuote),
+}
+
+// Each element carries its own `type` tag, so serialization delegates to it.
+impl Serialize for RichTextElement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Section(el) => el.serialize(serializer),
+            Self::List(el) => el.serialize(serializer),
+            Self::Preformatted(el) => el.serialize(serializer),
+            Self::Quote(el) => el.serialize(serializer),
+        }
+    }
+}
+
+/// A `rich_text_section`: a paragraph of inline elements.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "rich_text_section")]
+#[slaq_macros::builder]
+pub struct RichTextSection {
+    /// Inline elements of the section.
+    pub elements: Vec<RichTextInline>,
+}
+
+/// A `rich_text_list`. Nested lists are sibling lists with a larger `indent`.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "rich_text_list")]
+#[slaq_macros::builder]
+pub struct RichTextList {
+    /// Whether the list is bulleted or ordered.
+    pub style: ListStyle,
+    /// The list items, one section each.
+    pub elements: Vec<RichTextSection>,
+    /// Nesting level of the list, starting at 0.
+    pub indent: Option<u32>,
+    /// Number of items before the first one, for ordered lists continuing an earlier list.
+    pub offset: Option<u32>,
+    /// Width of the border to the left of the list, 0 or 1.
+    pub border: Option<u32>,
+}
+
+/// Bullet style of a `rich_text_list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListStyle {
+    /// A bulleted list.
+    Bullet,
+    /// A numbered list.
+    Ordered,
+}
+
+/// A `rich_text_preformatted` code block.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "rich_text_preformatted")]
+#[slaq_macros::builder]
+pub struct RichTextPreformatted {
+    /// Inline elements of the code block, usually plain text.
+    pub elements: Vec<RichTextInline>,
+    /// Width of the border to the left of the block, 0 or 1.
+    pub border: Option<u32>,
+}
+
+/// A `rich_text_quote` block quote.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "rich_text_quote")]
+#[slaq_macros::builder]
+pub struct RichTextQuote {
+    /// Inline elements of the quote.
+    pub elements: Vec<RichTextInline>,
+    /// Width of the border to the left of the quote, 0 or 1.
+    pub border: Option<u32>,
+}
+
+/// An inline element of a section, code block or quote.
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RichTextInline {
+    /// Styled text.
+    Text {
+        text: String,
+        style: Option<TextStyle>,
+    },
+    /// A hyperlink, showing `text` if set and the URL otherwise.
+    Link {
+        url: String,
+        text: Option<String>,
+        style: Option<TextStyle>,
+    },
+    /// A user mention.
+    User {
+        user_id: UserId,
+        style: Option<TextStyle>,
+    },
+    /// A channel mention.
+    Channel {
+        channel_id: ChannelId,
+        style: Option<TextStyle>,
+    },
+    /// A user group mention.
+    Usergroup {
+        usergroup_id: String,
+        style: Option<TextStyle>,
+    },
+    /// An emoji, by name without colons.
+    Emoji {
+        name: String,
+        unicode: Option<String>,
+    },
+    /// A date formatted in the reader's time zone.
+    Date {
+        /// Unix timestamp of the date.
+        timestamp: i64,
+        /// Format string with tokens like `{date_short}` or `{time}`.
+        format: String,
+        url: Option<String>,
+        fallback: Option<String>,
+    },
+    /// A broadcast mention: `here`, `channel` or `everyone`.
+    Broadcast { range: String },
+    /// A color swatch, for a hex value like `#F405B3`.
+    Color { value: String },
+}
+
+impl RichTextInline {
+    /// Creates unstyled text.
+    #[must_use]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text {
+            text: text.into(),
+            style: None,
+        }
+    }
+
+    /// Creates styled text.
+    #[must_use]
+    pub fn styled(text: impl Into<String>, style: TextStyle) -> Self {
+        Self::Text {
+            text: text.into(),
+            style: (!style.is_plain()).then_some(style),
+        }
+    }
+
+    /// Creates a link with an optional label.
+    #[must_use]
+    pub fn link(url: impl Into<String>, text: Option<String>) -> Self {
+        Self::Link {
+            url: url.into(),
+            text,
+            style: None,
+        }
+    }
+
+    /// Creates a user mention.
+    #[must_use]
+    pub fn user(user_id: impl Into<UserId>) -> Self {
+        Self::User {
+            user_id: user_id.into(),
+            style: None,
+        }
+    }
+
+    /// Creates a channel mention.
+    #[must_use]
+    pub fn channel(channel_id: impl Into<ChannelId>) -> Self {
+        Self::Channel {
+            channel_id: channel_id.into(),
+            style: None,
+        }
+    }
+
+    /// Creates an emoji by name, without colons.
+    #[must_use]
+    pub fn emoji(name: impl Into<String>) -> Self {
+        Self::Emoji {
+            name: name.into(),
+            unicode: None,
+        }
+    }
+}
+
+/// Text styling flags for inline elements.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct TextStyle {
+    /// Bold text.
+    pub bold: Option<bool>,
+    /// Italic text.
+    pub italic: Option<bool>,
+    /// Strikethrough text.
+    pub strike: Option<bool>,
+    /// Inline code.
+    pub code: Option<bool>,
+}
+
+impl TextStyle {
+    /// Returns `true` if no style flag is set.
+    #[must_use]
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn elements_serialize_with_slack_type_names() {
+        let elements = vec![
+            RichTextElement::Section(RichTextSection::new(vec![
+                RichTextInline::styled(
+                    "Hello ",
+                    TextStyle {
+                        bold: Some(true),
+                        ..TextStyle::default()
+                    },
+                ),
+                RichTextInline::user("U123"),
+            ])),
+            RichTextElement::List(
+                RichTextList::new(
+                    ListStyle::Ordered,
+                    vec![RichTextSection::new(vec![RichTextInline::text("one")])],
+                )
+                .indent(1u32),
+            ),
+        ];
+        assert_eq!(
+            serde_json::to_value(&elements).unwrap(),
+            json!([
+                {
+                    "type": "rich_text_section",
+                    "elements": [
+                        {"type": "text", "text": "Hello ", "style": {"bold": true}},
+                        {"type": "user", "user_id": "U123"}
+                    ]
+                },
+                {
+                    "type": "rich_text_list",
+                    "style": "ordered",
+                    "indent": 1,
+                    "elements": [{
+                        "type": "rich_text_section",
+                        "elements": [{"type": "text", "text": "one"}]
+                    }]
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn elements_deserialize_from_slack_payload() {
+        let raw = json!([
+            {"type": "rich_text_quote", "elements": [{"type": "text", "text": "quoted"}]},
+            {"type": "rich_text_preformatted", "elements": [{"type": "text", "text": "code"}], "border": 0},
+            {"type": "rich_text_section", "elements": [
+                {"type": "emoji", "name": "wave", "unicode": "1f44b"},
+                {"type": "broadcast", "range": "here"},
+                {"type": "date", "timestamp": 1_700_000_000, "format": "{date_short}"}
+            ]}
+        ]);
+        let elements: Vec<RichTextElement> = serde_json::from_value(raw.clone()).unwrap();
+        assert!(matches!(elements[0], RichTextElement::Quote(_)));
+        assert!(matches!(
+            &elements[1],
+            RichTextElement::Preformatted(p) if p.border == Some(0)
+        ));
+        assert_eq!(serde_json::to_value(&elements).unwrap(), raw);
+    }
+}
@@ -0,0 +1,414 @@
+//! Client-side checks of Slack's documented Block Kit limits.
+//!
+//! Slack answers invalid blocks with a bare `invalid_blocks` error. These
+//! checks catch the common causes before sending and report the JSON path
+//! of each offending field, e.g. `blocks[2].text.text`.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use thiserror::Error;
+
+use super::{Block, Element, Text};
+
+/// Where blocks are displayed, which decides how many are allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Surface {
+    /// A message: up to 50 blocks.
+    #[default]
+    Message,
+    /// A modal view: up to 100 blocks.
+    Modal,
+    /// An App Home tab: up to 100 blocks.
+    HomeTab,
+}
+
+impl Surface {
+    /// Maximum number of blocks on this surface.
+    #[must_use]
+    pub fn max_blocks(self) -> usize {
+        match self {
+            Surface::Message => 50,
+            Surface::Modal | Surface::HomeTab => 100,
+        }
+    }
+}
+
+/// A Block Kit limit violated at `path`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{path}: {kind}")]
+pub struct BlockError {
+    /// JSON path of the offending field, e.g. `blocks[0].block_id`.
+    pub path: String,
+    /// The violated limit.
+    pub kind: BlockErrorKind,
+}
+
+/// Kinds of Block Kit limit violations.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BlockErrorKind {
+    #[error("{count} items exceed the limit of {max}")]
+    TooMany { count: usize, max: usize },
+    #[error("{len} characters exceed the limit of {max}")]
+    TooLong { len: usize, max: usize },
+    #[error("required field is missing or empty")]
+    Missing,
+    #[error("block_id `{0}` is used by another block")]
+    DuplicateBlockId(String),
+    #[error("action_id `{0}` is used by another element in the same block")]
+    DuplicateActionId(String),
+}
+
+/// Errors found by [`validate`], in the order they occur.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockErrors(pub Vec<BlockError>);
+
+impl fmt::Display for BlockErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BlockErrors {}
+
+/// Checks `blocks` against Slack's limits for messages.
+///
+/// # Errors
+/// Returns every violation found, each with the path of the offending field.
+pub fn validate(blocks: &[Block]) -> Result<(), BlockErrors> {
+    validate_for(blocks, Surface::Message)
+}
+
+/// Checks `blocks` against Slack's limits for the given surface.
+///
+/// Covers the block count, `block_id` length and uniqueness, text lengths of
+/// sections, headers and inputs, the combined text length of all `markdown`
+/// blocks, element counts, `action_id` length and uniqueness within each
+/// block, and image alt text. Blocks and elements kept as raw JSON
+/// (`Block::Unknown`, `Element::Unknown`) are only checked for their
+/// `block_id` and `action_id`.
+///
+/// # Errors
+/// Returns every violation found, each with the path of the offending field.
+pub fn validate_for(blocks: &[Block], surface: Surface) -> Result<(), BlockErrors> {
+    let mut validator = Validator::default();
+    validator.max_items("blocks", blocks.len(), surface.max_blocks());
+    for (i, block) in blocks.iter().enumerate() {
+        validator.block(&format!("blocks[{i}]"), block);
+    }
+    if validator.errors.is_empty() {
+        Ok(())
+    } else {
+        Err(BlockErrors(validator.errors))
+    }
+}
+
+/// The `text` of a text object.
+fn text_of(text: &Text) -> &str {
+    match text {
+        Text::PlainText(plain) => &plain.text,
+        Text::Mrkdwn(mrkdwn) => &mrkdwn.text,
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<BlockError>,
+    block_ids: HashSet<String>,
+    action_ids: HashSet<String>,
+    markdown_len: usize,
+}
+
+impl Validator {
+    fn error(&mut self, path: String, kind: BlockErrorKind) {
+        self.errors.push(BlockError { path, kind });
+    }
+
+    fn max_items(&mut self, path: &str, count: usize, max: usize) {
+        if count > max {
+            self.error(path.to_owned(), BlockErrorKind::TooMany { count, max });
+        }
+    }
+
+    fn max_len(&mut self, path: String, text: &str, max: usize) {
+        let len = text.chars().count();
+        if len > max {
+            self.error(path, BlockErrorKind::TooLong { len, max });
+        }
+    }
+
+    /// Checks the length of a text object's `text`, if present.
+    fn text_object(&mut self, path: &str, field: &str, text: Option<&Text>, max: usize) {
+        if let Some(text) = text {
+            self.max_len(format!("{path}.{field}.text"), text_of(text), max);
+        }
+    }
+
+    fn required(&mut self, path: String, value: &str) {
+        if value.is_empty() {
+            self.error(path, BlockErrorKind::Missing);
+        }
+    }
+
+    fn block(&mut self, path: &str, block: &Block) {
+        if let Some(block_id) = block.block_id() {
+            self.max_len(format!("{path}.block_id"), block_id, 255);
+            if !self.block_ids.insert(block_id.to_owned()) {
+                self.error(
+                    format!("{path}.block_id"),
+                    BlockErrorKind::DuplicateBlockId(block_id.to_owned()),
+                );
+            }
+        }
+        self.action_ids.clear();
+
+        match block {
+            Block::Section(section) => {
+                if section.text.is_none() && section.fields.is_none() {
+                    self.error(format!("{path}.text"), BlockErrorKind::Missing);
+                }
+                self.text_object(path, "text", section.text.as_ref(), 3000);
+                if let Some(fields) = &section.fields {
+                    self.max_items(&format!("{path}.fields"), fields.len(), 10);
+                    for (i, field) in fields.iter().enumerate() {
+                        self.max_len(format!("{path}.fields[{i}].text"), text_of(field), 2000);
+                    }
+                }
+                if let Some(accessory) = &section.accessory {
+                    self.element(&format!("{path}.accessory"), accessory);
+                }
+            }
+            Block::Header(header) => {
+                self.max_len(format!("{path}.text.text"), &header.text.text, 150);
+            }
+            Block::Markdown(markdown) => self.markdown(path, &markdown.text),
+            Block::Image(image) => {
+                self.required(format!("{path}.alt_text"), &image.alt_text);
+                self.max_len(format!("{path}.alt_text"), &image.alt_text, 2000);
+                if let Some(url) = &image.image_url {
+                    self.max_len(format!("{path}.image_url"), url, 3000);
+                }
+                if let Some(title) = &image.title {
+                    self.max_len(format!("{path}.title.text"), &title.text, 2000);
+                }
+            }
+            Block::Video(video) => {
+                self.required(format!("{path}.alt_text"), &video.alt_text);
+                self.max_len(format!("{path}.title.text"), &video.title.text, 200);
+            }
+            Block::Input(input) => {
+                self.max_len(format!("{path}.label.text"), &input.label.text, 2000);
+                if let Some(hint) = &input.hint {
+                    self.max_len(format!("{path}.hint.text"), &hint.text, 2000);
+                }
+                self.element(&format!("{path}.element"), &input.element);
+            }
+            Block::Actions(actions) => self.elements(path, &actions.elements, 25),
+            Block::Context(context) => self.elements(path, &context.elements, 10),
+            Block::Divider(_) | Block::File(_) | Block::RichText(_) | Block::Unknown(_) => {}
+        }
+    }
+
+    /// Checks the text of a `markdown` block. The limit applies to all
+    /// `markdown` blocks combined, and is reported at the block that crosses it.
+    fn markdown(&mut self, path: &str, text: &str) {
+        const MAX: usize = 12_000;
+        let before = self.markdown_len;
+        self.markdown_len += text.chars().count();
+        if before <= MAX && self.markdown_len > MAX {
+            let len = self.markdown_len;
+            self.error(
+                format!("{path}.text"),
+                BlockErrorKind::TooLong { len, max: MAX },
+            );
+        }
+    }
+
+    fn elements(&mut self, path: &str, elements: &[Element], max: usize) {
+        self.max_items(&format!("{path}.elements"), elements.len(), max);
+        for (i, element) in elements.iter().enumerate() {
+            self.element(&format!("{path}.elements[{i}]"), element);
+        }
+    }
+
+    fn element(&mut self, path: &str, element: &Element) {
+        if let Some(action_id) = element.action_id() {
+            self.max_len(format!("{path}.action_id"), action_id, 255);
+            if !self.action_ids.insert(action_id.to_owned()) {
+                self.error(
+                    format!("{path}.action_id"),
+                    BlockErrorKind::DuplicateActionId(action_id.to_owned()),
+                );
+            }
+        }
+        match element {
+            Element::ImageElement(image) => {
+                self.required(format!("{path}.alt_text"), &image.alt_text);
+            }
+            Element::Button(button) => {
+                self.max_len(format!("{path}.text.text"), &button.text.text, 75);
+                if let Some(value) = &button.value {
+                    self.max_len(format!("{path}.value"), value, 2000);
+                }
+            }
+            Element::WorkflowButton(button) => {
+                self.max_len(format!("{path}.text.text"), &button.text.text, 75);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocks::{Actions, Button, Divider, Element, Image, Markdown, Section, Text};
+
+    fn kinds(blocks: &[Block]) -> Vec<(String, BlockErrorKind)> {
+        validate(blocks)
+            .unwrap_err()
+            .0
+            .into_iter()
+            .map(|e| (e.path, e.kind))
+            .collect()
+    }
+
+    #[test]
+    fn valid_blocks_pass() {
+        let blocks = vec![
+            Section::new()
+                .text(Text::mrkdwn("hi"))
+                .block_id("a")
+                .build(),
+            Divider::new().block_id("b").build(),
+            Actions::new(vec![
+                Button::new("Yes").action_id("yes").build(),
+                Button::new("No").action_id("no").build(),
+            ])
+            .build(),
+        ];
+        assert_eq!(validate(&blocks), Ok(()));
+    }
+
+    #[test]
+    fn block_count_depends_on_surface() {
+        let blocks = vec![Divider::new().build(); 60];
+        assert_eq!(
+            kinds(&blocks),
+            vec![(
+                "blocks".to_owned(),
+                BlockErrorKind::TooMany { count: 60, max: 50 }
+            )]
+        );
+        assert_eq!(validate_for(&blocks, Surface::Modal), Ok(()));
+    }
+
+    #[test]
+    fn reports_paths_of_each_violation() {
+        let blocks = vec![
+            Section::new()
+                .text(Text::mrkdwn("x".repeat(3001)))
+                .block_id("dup")
+                .build(),
+            Markdown::new("y".repeat(12_001)).build(),
+            Image::new("")
+                .image_url("https://example.com/a.png")
+                .block_id("dup")
+                .build(),
+            Actions::new(vec![
+                Button::new("A").action_id("go").build(),
+                Element::image("https://example.com/b.png", ""),
+                Button::new("B").action_id("go").build(),
+            ])
+            .build(),
+        ];
+        assert_eq!(
+            kinds(&blocks),
+            vec![
+                (
+                    "blocks[0].text.text".to_owned(),
+                    BlockErrorKind::TooLong {
+                        len: 3001,
+                        max: 3000
+                    }
+                ),
+                (
+                    "blocks[1].text".to_owned(),
+                    BlockErrorKind::TooLong {
+                        len: 12_001,
+                        max: 12_000
+                    }
+                ),
+                (
+                    "blocks[2].block_id".to_owned(),
+                    BlockErrorKind::DuplicateBlockId("dup".to_owned())
+                ),
+                ("blocks[2].alt_text".to_owned(), BlockErrorKind::Missing),
+                (
+                    "blocks[3].elements[1].alt_text".to_owned(),
+                    BlockErrorKind::Missing
+                ),
+                (
+                    "blocks[3].elements[2].action_id".to_owned(),
+                    BlockErrorKind::DuplicateActionId("go".to_owned())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn markdown_limit_applies_to_all_markdown_blocks() {
+        let half = || Markdown::new("y".repeat(6000)).build();
+        assert_eq!(validate(&[half(), half()]), Ok(()));
+        assert_eq!(
+            kinds(&[half(), Divider::new().build(), half(), half()]),
+            vec![(
+                "blocks[3].text".to_owned(),
+                BlockErrorKind::TooLong {
+                    len: 18_000,
+                    max: 12_000
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn errors_display_with_paths() {
+        let err = validate(&[Section::new().build()]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "blocks[0].text: required field is missing or empty"
+        );
+    }
+
+    #[test]
+    fn raw_blocks_are_checked_for_ids() {
+        let raw: Vec<Block> = serde_json::from_value(serde_json::json!([
+            {"type": "table", "block_id": "t"},
+            {"type": "actions", "block_id": "t", "elements": [
+                {"type": "rich_text_input", "action_id": "a"},
+                {"type": "button", "text": {"type": "plain_text", "text": "B"}, "action_id": "a"}
+            ]}
+        ]))
+        .unwrap();
+        assert_eq!(
+            kinds(&raw),
+            vec![
+                (
+                    "blocks[1].block_id".to_owned(),
+                    BlockErrorKind::DuplicateBlockId("t".to_owned())
+                ),
+                (
+                    "blocks[1].elements[1].action_id".to_owned(),
+                    BlockErrorKind::DuplicateActionId("a".to_owned())
+                ),
+            ]
+        );
+    }
+}
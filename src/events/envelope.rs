@@ -0,0 +1,125 @@
+//! The outer payloads the Events API delivers.
+
+use serde::Deserialize;
+
+use super::Event;
+use crate::id::{EnterpriseId, TeamId, UserId};
+
+/// A request body sent to an Events API request URL.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Envelope {
+    /// Sent once when the request URL is configured; respond with the
+    /// challenge.
+    UrlVerification(UrlVerification),
+    /// An event the app is subscribed to.
+    EventCallback(Box<EventCallback>),
+    /// Sent when the app's event deliveries are rate limited.
+    AppRateLimited(AppRateLimited),
+    /// An envelope type this crate doesn't know yet.
+    #[serde(other)]
+    Unknown,
+}
+
+/// The handshake Slack sends to confirm a request URL.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UrlVerification {
+    /// Respond with this value to complete the handshake.
+    pub challenge: String,
+    /// Deprecated verification token; verify signatures instead.
+    pub token: Option<String>,
+}
+
+/// An event delivered to the app, with where and for whom it happened.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventCallback {
+    /// The event itself.
+    pub event: Event,
+    /// Unique ID of this event, the same across retries.
+    pub event_id: String,
+    /// Unix time the event was dispatched.
+    pub event_time: i64,
+    /// Workspace the event happened in.
+    pub team_id: TeamId,
+    /// ID of the app the event is for.
+    pub api_app_id: String,
+    /// Enterprise Grid organization the event happened in.
+    pub enterprise_id: Option<EnterpriseId>,
+    /// An installation of the app that can see the event. Slack lists one;
+    /// call `apps.event.authorizations.list` with `event_context` for all.
+    #[serde(default)]
+    pub authorizations: Vec<Authorization>,
+    /// Identifies the event for `apps.event.authorizations.list`.
+    pub event_context: Option<String>,
+    /// Whether the event happened in a channel shared with other
+    /// organizations.
+    pub is_ext_shared_channel: Option<bool>,
+    /// Workspace the event's channel belongs to, for shared channels.
+    pub context_team_id: Option<TeamId>,
+    /// Organization the event's channel belongs to, for shared channels.
+    pub context_enterprise_id: Option<EnterpriseId>,
+    /// Deprecated verification token; verify signatures instead.
+    pub token: Option<String>,
+}
+
+/// An installation of the app that an event is visible to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Authorization {
+    /// Organization of the installation.
+    pub enterprise_id: Option<EnterpriseId>,
+    /// Workspace of the installation.
+    pub team_id: Option<TeamId>,
+    /// User the installation's token acts as.
+    pub user_id: UserId,
+    /// Whether the token is a bot token.
+    pub is_bot: bool,
+    /// Whether the app is installed across the whole organization.
+    #[serde(default)]
+    pub is_enterprise_install: bool,
+}
+
+/// Notice that events are being dropped because the app exceeded 30,000
+/// deliveries per workspace per hour.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppRateLimited {
+    /// Workspace whose events are rate limited.
+    pub team_id: TeamId,
+    /// ID of the app.
+    pub api_app_id: String,
+    /// Unix time of the minute the limit was reached.
+    pub minute_rate_limited: i64,
+    /// Deprecated verification token; verify signatures instead.
+    pub token: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_types_deserialize() {
+        let challenge: Envelope = serde_json::from_str(
+            r#"{"token":"t","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P","type":"url_verification"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            challenge,
+            Envelope::UrlVerification(UrlVerification { challenge, .. }) if challenge.starts_with("3eZb")
+        ));
+
+        let limited: Envelope = serde_json::from_str(
+            r#"{"token":"t","type":"app_rate_limited","team_id":"T1","minute_rate_limited":1518467820,"api_app_id":"A1"}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            limited,
+            Envelope::AppRateLimited(AppRateLimited {
+                minute_rate_limited: 1_518_467_820,
+                ..
+            })
+        ));
+
+        let future: Envelope = serde_json::from_str(r#"{"type":"something_new"}"#).unwrap();
+        assert!(matches!(future, Envelope::Unknown));
+    }
+}
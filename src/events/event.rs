@@ -0,0 +1,404 @@
+//! Typed inner events of an `event_callback`.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use serde_json as json;
+
+use super::MessageEvent;
+use crate::api::chat::unfurl::{UnfurlSource, UnfurlTarget};
+use crate::blocks::Block;
+use crate::id::{ChannelId, TeamId, Ts, UserId};
+
+/// An event from the Events API, keyed by its `type`.
+///
+/// Event types this crate doesn't model, and known types whose payload
+/// doesn't parse, are kept as `Unknown` with the raw JSON.
+#[derive(Debug, Clone)]
+pub enum Event {
+    /// `message`: a message was posted, edited or deleted.
+    Message(Box<MessageEvent>),
+    /// `app_mention`: the app was mentioned in a message.
+    AppMention(Box<AppMentionEvent>),
+    /// `reaction_added`: a reaction was added to an item.
+    ReactionAdded(ReactionEvent),
+    /// `reaction_removed`: a reaction was removed from an item.
+    ReactionRemoved(ReactionEvent),
+    /// `member_joined_channel`: a user joined a channel.
+    MemberJoinedChannel(MemberChannelEvent),
+    /// `member_left_channel`: a user left a channel.
+    MemberLeftChannel(MemberChannelEvent),
+    /// `channel_created`: a public channel was created.
+    ChannelCreated(ChannelCreatedEvent),
+    /// `channel_rename`: a channel was renamed.
+    ChannelRename(ChannelRenameEvent),
+    /// `channel_archive`: a channel was archived.
+    ChannelArchive(ChannelEvent),
+    /// `channel_unarchive`: a channel was unarchived.
+    ChannelUnarchive(ChannelEvent),
+    /// `channel_deleted`: a channel was deleted.
+    ChannelDeleted(ChannelEvent),
+    /// `app_home_opened`: a user opened the app's home.
+    AppHomeOpened(AppHomeOpenedEvent),
+    /// `link_shared`: a link matching the app's unfurl domains was shared.
+    LinkShared(LinkSharedEvent),
+    /// `team_join`: a user joined the workspace.
+    TeamJoin(TeamJoinEvent),
+    /// `app_uninstalled`: the app was uninstalled.
+    AppUninstalled,
+    /// `tokens_revoked`: tokens for the app were revoked.
+    TokensRevoked(TokensRevokedEvent),
+    /// Any other event, as raw JSON.
+    Unknown(json::Value),
+}
+
+impl Event {
+    /// The event's `type`, e.g. `app_mention`.
+    #[must_use]
+    pub fn kind(&self) -> &str {
+        match self {
+            Event::Message(_) => "message",
+            Event::AppMention(_) => "app_mention",
+            Event::ReactionAdded(_) => "reaction_added",
+            Event::ReactionRemoved(_) => "reaction_removed",
+            Event::MemberJoinedChannel(_) => "member_joined_channel",
+            Event::MemberLeftChannel(_) => "member_left_channel",
+            Event::ChannelCreated(_) => "channel_created",
+            Event::ChannelRename(_) => "channel_rename",
+            Event::ChannelArchive(_) => "channel_archive",
+            Event::ChannelUnarchive(_) => "channel_unarchive",
+            Event::ChannelDeleted(_) => "channel_deleted",
+            Event::AppHomeOpened(_) => "app_home_opened",
+            Event::LinkShared(_) => "link_shared",
+            Event::TeamJoin(_) => "team_join",
+            Event::AppUninstalled => "app_uninstalled",
+            Event::TokensRevoked(_) => "tokens_revoked",
+            Event::Unknown(value) => value
+                .get("type")
+                .and_then(json::Value::as_str)
+                .unwrap_or(""),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = json::Value::deserialize(deserializer)?;
+        let event = match value.get("type").and_then(json::Value::as_str) {
+            Some("message") => typed(&value, |e| Event::Message(Box::new(e))),
+            Some("app_mention") => typed(&value, |e| Event::AppMention(Box::new(e))),
+            Some("reaction_added") => typed(&value, Event::ReactionAdded),
+            Some("reaction_removed") => typed(&value, Event::ReactionRemoved),
+            Some("member_joined_channel") => typed(&value, Event::MemberJoinedChannel),
+            Some("member_left_channel") => typed(&value, Event::MemberLeftChannel),
+            Some("channel_created") => typed(&value, Event::ChannelCreated),
+            Some("channel_rename") => typed(&value, Event::ChannelRename),
+            Some("channel_archive") => typed(&value, Event::ChannelArchive),
+            Some("channel_unarchive") => typed(&value, Event::ChannelUnarchive),
+            Some("channel_deleted") => typed(&value, Event::ChannelDeleted),
+            Some("app_home_opened") => typed(&value, Event::AppHomeOpened),
+            Some("link_shared") => typed(&value, Event::LinkShared),
+            Some("team_join") => typed(&value, Event::TeamJoin),
+            Some("app_uninstalled") => Some(Event::AppUninstalled),
+            Some("tokens_revoked") => typed(&value, Event::TokensRevoked),
+            _ => None,
+        };
+        Ok(event.unwrap_or(Event::Unknown(value)))
+    }
+}
+
+fn typed<T: DeserializeOwned>(
+    value: &json::Value,
+    variant: impl FnOnce(T) -> Event,
+) -> Option<Event> {
+    json::from_value(value.clone()).ok().map(variant)
+}
+
+/// A message mentioning the app.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppMentionEvent {
+    /// ID of the user who posted the message.
+    pub user: Option<UserId>,
+    /// ID of the bot that posted the message.
+    pub bot_id: Option<String>,
+    /// Text of the message, including the mention.
+    pub text: String,
+    /// Timestamp of the message.
+    pub ts: Ts,
+    /// Conversation the message is in.
+    pub channel: ChannelId,
+    /// Timestamp of the event.
+    pub event_ts: Ts,
+    /// Timestamp of the thread parent, when the mention is in a thread.
+    pub thread_ts: Option<Ts>,
+    /// Workspace of the author.
+    pub team: Option<TeamId>,
+    /// Structured blocks of the message.
+    pub blocks: Option<Vec<Block>>,
+}
+
+impl AppMentionEvent {
+    /// The thread to reply in: the mention's thread, or a new one under the
+    /// mention.
+    #[must_use]
+    pub fn reply_thread_ts(&self) -> Ts {
+        self.thread_ts.unwrap_or(self.ts)
+    }
+}
+
+/// A reaction added to or removed from an item.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReactionEvent {
+    /// ID of the user who reacted.
+    pub user: UserId,
+    /// Emoji name, without colons.
+    pub reaction: String,
+    /// ID of the user who created the item.
+    pub item_user: Option<UserId>,
+    /// The item reacted to.
+    pub item: ReactionItem,
+    /// Timestamp of the event.
+    pub event_ts: Ts,
+}
+
+/// The item a reaction is on.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReactionItem {
+    /// A message.
+    Message {
+        /// Conversation of the message.
+        channel: ChannelId,
+        /// Timestamp of the message.
+        ts: Ts,
+    },
+    /// A file.
+    File {
+        /// File ID.
+        file: String,
+    },
+    /// A file comment.
+    FileComment {
+        /// File ID.
+        file: String,
+        /// Comment ID.
+        file_comment: String,
+    },
+}
+
+/// A user joining or leaving a channel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemberChannelEvent {
+    /// ID of the user.
+    pub user: UserId,
+    /// ID of the channel.
+    pub channel: ChannelId,
+    /// Kind of channel: `C` for public, `G` for private.
+    pub channel_type: Option<String>,
+    /// Workspace of the user.
+    pub team: Option<TeamId>,
+    /// ID of the user who invited them, when invited.
+    pub inviter: Option<UserId>,
+    /// Timestamp of the event.
+    pub event_ts: Option<Ts>,
+}
+
+/// A newly created channel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelCreatedEvent {
+    /// The channel.
+    pub channel: CreatedChannel,
+}
+
+/// The channel of a `channel_created` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreatedChannel {
+    /// ID of the channel.
+    pub id: ChannelId,
+    /// Name of the channel.
+    pub name: String,
+    /// Unix time the channel was created.
+    pub created: i64,
+    /// ID of the user who created it.
+    pub creator: Option<UserId>,
+}
+
+/// A renamed channel.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelRenameEvent {
+    /// The channel, with its new name.
+    pub channel: RenamedChannel,
+}
+
+/// The channel of a `channel_rename` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RenamedChannel {
+    /// ID of the channel.
+    pub id: ChannelId,
+    /// New name of the channel.
+    pub name: String,
+    /// Unix time the channel was created.
+    pub created: Option<i64>,
+}
+
+/// A channel archived, unarchived or deleted.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChannelEvent {
+    /// ID of the channel.
+    pub channel: ChannelId,
+    /// ID of the user who made the change.
+    pub user: Option<UserId>,
+}
+
+/// A user opening a tab of the app's home.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AppHomeOpenedEvent {
+    /// ID of the user.
+    pub user: UserId,
+    /// The direct message channel between the user and the app.
+    pub channel: ChannelId,
+    /// The tab opened: `home`, `messages` or `about`.
+    pub tab: String,
+    /// Timestamp of the event.
+    pub event_ts: Ts,
+    /// The home view currently published for the user, as raw JSON.
+    pub view: Option<json::Value>,
+}
+
+/// Links shared in a message or the message composer.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LinkSharedEvent {
+    /// Conversation of the message, or `COMPOSER` when not yet posted.
+    pub channel: ChannelId,
+    /// ID of the user who shared the links.
+    pub user: UserId,
+    /// Timestamp of the message, or an opaque ID when not yet posted.
+    pub message_ts: String,
+    /// Timestamp of the thread parent, when the message is in a thread.
+    pub thread_ts: Option<Ts>,
+    /// The links matching the app's unfurl domains.
+    pub links: Vec<SharedLink>,
+    /// ID of the links to pass to `chat.unfurl`.
+    pub unfurl_id: Option<String>,
+    /// Where the links were shared.
+    pub source: Option<UnfurlSource>,
+    /// Timestamp of the event.
+    pub event_ts: Ts,
+}
+
+impl LinkSharedEvent {
+    /// The target to pass to `chat.unfurl` for these links: the unfurl ID
+    /// when Slack sent one, otherwise the posted message. `None` if neither
+    /// is available, as `message_ts` isn't a timestamp before posting.
+    #[must_use]
+    pub fn unfurl_target(&self) -> Option<UnfurlTarget> {
+        if let (Some(unfurl_id), Some(source)) = (&self.unfurl_id, self.source) {
+            return Some(UnfurlTarget::unfurl_id(unfurl_id.clone(), source));
+        }
+        let ts: Ts = self.message_ts.parse().ok()?;
+        Some(UnfurlTarget::message(self.channel.clone(), ts))
+    }
+}
+
+/// A link in a `link_shared` event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharedLink {
+    /// Domain of the link.
+    pub domain: String,
+    /// The full URL.
+    pub url: String,
+}
+
+/// A user joining the workspace.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TeamJoinEvent {
+    /// The new user.
+    pub user: EventUser,
+}
+
+/// A user as included in events like `team_join`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventUser {
+    /// ID of the user.
+    pub id: UserId,
+    /// Workspace of the user.
+    pub team_id: Option<TeamId>,
+    /// Username.
+    pub name: Option<String>,
+    /// Full name.
+    pub real_name: Option<String>,
+    /// Whether the user is deactivated.
+    #[serde(default)]
+    pub deleted: bool,
+    /// Whether the user is a bot.
+    #[serde(default)]
+    pub is_bot: bool,
+    /// Time zone name, e.g. `Europe/London`.
+    pub tz: Option<String>,
+    /// Time zone offset from UTC in seconds.
+    pub tz_offset: Option<i32>,
+    /// The user's profile, as raw JSON.
+    pub profile: Option<json::Value>,
+}
+
+/// Tokens revoked for the app.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TokensRevokedEvent {
+    /// The revoked tokens, by kind.
+    pub tokens: RevokedTokens,
+}
+
+/// The users whose tokens were revoked, by token kind.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RevokedTokens {
+    /// Users whose user tokens were revoked.
+    #[serde(default)]
+    pub oauth: Vec<UserId>,
+    /// Bot users whose bot tokens were revoked.
+    #[serde(default)]
+    pub bot: Vec<UserId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_events_are_typed() {
+        let event: Event = serde_json::from_str(
+            r#"{"type":"reaction_added","user":"U1","reaction":"thumbsup","item_user":"U2",
+                "item":{"type":"message","channel":"C1","ts":"1360782400.498405"},
+                "event_ts":"1360782804.083113"}"#,
+        )
+        .unwrap();
+        let Event::ReactionAdded(reaction) = event else {
+            panic!("expected reaction_added, got {event:?}");
+        };
+        assert_eq!(reaction.reaction, "thumbsup");
+        assert!(matches!(
+            reaction.item,
+            ReactionItem::Message { channel, .. } if channel == "C1"
+        ));
+
+        let event: Event = serde_json::from_str(
+            r#"{"type":"app_mention","user":"U1","text":"<@U0LAN0Z89> hi","ts":"1515449522.000016",
+                "channel":"C1","event_ts":"1515449522.000016"}"#,
+        )
+        .unwrap();
+        assert_eq!(event.kind(), "app_mention");
+        let Event::AppMention(mention) = event else {
+            panic!("expected app_mention");
+        };
+        assert_eq!(mention.reply_thread_ts(), mention.ts);
+    }
+
+    #[test]
+    fn unknown_and_unparseable_events_keep_raw_json() {
+        let event: Event =
+            serde_json::from_str(r#"{"type":"emoji_changed","subtype":"add"}"#).unwrap();
+        assert_eq!(event.kind(), "emoji_changed");
+        // `reaction_added` without its required fields.
+        let event: Event = serde_json::from_str(r#"{"type":"reaction_added"}"#).unwrap();
+        assert!(matches!(event, Event::Unknown(_)));
+        assert_eq!(event.kind(), "reaction_added");
+    }
+}
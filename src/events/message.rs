@@ -0,0 +1,210 @@
+//! The `message` event and its subtypes.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::api::attachment::Attachment;
+use crate::api::message::{Edited, File, Message};
+use crate::api::metadata::Metadata;
+use crate::blocks::Block;
+use crate::id::{ChannelId, TeamId, Ts, UserId};
+
+/// A message posted, edited or deleted in a conversation the app can see.
+///
+/// `subtype` tells plain messages from bot messages, edits, deletions,
+/// joins and the like. Edits carry the new version in `message` and
+/// deletions the removed timestamp in `deleted_ts`; both carry the old
+/// version in `previous_message`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MessageEvent {
+    /// The kind of message; `None` for a plain user message.
+    pub subtype: Option<MessageSubtype>,
+    /// Conversation the message is in.
+    pub channel: ChannelId,
+    /// Kind of conversation: `channel`, `group`, `im` or `mpim`.
+    pub channel_type: Option<String>,
+    /// Timestamp of the message. Unique within its channel.
+    pub ts: Ts,
+    /// Timestamp of the event.
+    pub event_ts: Option<Ts>,
+    /// Text of the message.
+    pub text: Option<String>,
+    /// ID of the user who posted the message.
+    pub user: Option<UserId>,
+    /// ID of the bot that posted the message.
+    pub bot_id: Option<String>,
+    /// ID of the app that posted the message.
+    pub app_id: Option<String>,
+    /// Display name used when the message was posted with a custom `username`.
+    pub username: Option<String>,
+    /// Workspace of the author.
+    pub team: Option<TeamId>,
+    /// Structured blocks of the message.
+    pub blocks: Option<Vec<Block>>,
+    /// Legacy secondary attachments of the message.
+    pub attachments: Option<Vec<Attachment>>,
+    /// Files shared in the message.
+    pub files: Option<Vec<File>>,
+    /// Timestamp of the thread parent, set on parents and replies alike.
+    pub thread_ts: Option<Ts>,
+    /// ID of the user who posted the thread parent. Only set on replies.
+    pub parent_user_id: Option<UserId>,
+    /// Set when the message has been edited.
+    pub edited: Option<Edited>,
+    /// Message metadata.
+    pub metadata: Option<Metadata>,
+    /// Whether the message is hidden from the conversation, as edits and
+    /// deletions are.
+    #[serde(default)]
+    pub hidden: bool,
+    /// The new version of an edited message, for `message_changed`.
+    pub message: Option<Box<Message>>,
+    /// The version before the edit or deletion, for `message_changed` and
+    /// `message_deleted`.
+    pub previous_message: Option<Box<Message>>,
+    /// Timestamp of the deleted message, for `message_deleted`.
+    pub deleted_ts: Option<Ts>,
+}
+
+impl MessageEvent {
+    /// Whether this is a reply in a thread rather than a thread parent or a
+    /// top-level message.
+    #[must_use]
+    pub fn is_thread_reply(&self) -> bool {
+        self.thread_ts.is_some_and(|thread_ts| thread_ts != self.ts)
+    }
+
+    /// Whether the message was posted by a bot or app.
+    #[must_use]
+    pub fn is_bot(&self) -> bool {
+        self.bot_id.is_some() || self.subtype == Some(MessageSubtype::BotMessage)
+    }
+}
+
+macro_rules! message_subtypes {
+    ($($(#[$doc:meta])* $variant:ident = $name:literal,)*) => {
+        /// The `subtype` of a message event.
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum MessageSubtype {
+            $($(#[$doc])* $variant,)*
+            /// A subtype this crate doesn't know yet.
+            Other(String),
+        }
+
+        impl MessageSubtype {
+            /// The subtype as Slack sends it, e.g. `bot_message`.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $name,)*
+                    Self::Other(name) => name,
+                }
+            }
+        }
+
+        impl From<&str> for MessageSubtype {
+            fn from(name: &str) -> Self {
+                match name {
+                    $($name => Self::$variant,)*
+                    other => Self::Other(other.to_owned()),
+                }
+            }
+        }
+    };
+}
+
+message_subtypes! {
+    /// Posted by a bot or integration.
+    BotMessage = "bot_message",
+    /// Sent with `/me`.
+    MeMessage = "me_message",
+    /// An existing message was edited.
+    MessageChanged = "message_changed",
+    /// A message was deleted.
+    MessageDeleted = "message_deleted",
+    /// A thread reply was also posted to the channel.
+    ThreadBroadcast = "thread_broadcast",
+    /// A file was shared.
+    FileShare = "file_share",
+    /// A member joined the channel.
+    ChannelJoin = "channel_join",
+    /// A member left the channel.
+    ChannelLeave = "channel_leave",
+    /// The channel topic changed.
+    ChannelTopic = "channel_topic",
+    /// The channel purpose changed.
+    ChannelPurpose = "channel_purpose",
+    /// The channel was renamed.
+    ChannelName = "channel_name",
+    /// The channel was archived.
+    ChannelArchive = "channel_archive",
+    /// The channel was unarchived.
+    ChannelUnarchive = "channel_unarchive",
+    /// A message was pinned.
+    PinnedItem = "pinned_item",
+    /// A message was unpinned.
+    UnpinnedItem = "unpinned_item",
+    /// Content hidden by Enterprise Key Management.
+    EkmAccessDenied = "ekm_access_denied",
+    /// The channel was converted to private.
+    ChannelConvertToPrivate = "channel_convert_to_private",
+    /// A reminder was added with `/remind`.
+    ReminderAdd = "reminder_add",
+}
+
+impl fmt::Display for MessageSubtype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageSubtype {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(Self::from(name.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message_changed_carries_both_versions() {
+        let event: MessageEvent = serde_json::from_str(
+            r#"{
+                "type": "message",
+                "subtype": "message_changed",
+                "hidden": true,
+                "channel": "C1",
+                "ts": "1358878755.000001",
+                "message": {"type": "message", "user": "U1", "text": "Hello, world!", "ts": "1358878749.000002",
+                            "edited": {"user": "U1", "ts": "1358878755.000001"}},
+                "previous_message": {"type": "message", "user": "U1", "text": "Helo", "ts": "1358878749.000002"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(event.subtype, Some(MessageSubtype::MessageChanged));
+        assert!(event.hidden);
+        assert_eq!(
+            event.message.unwrap().text.as_deref(),
+            Some("Hello, world!")
+        );
+        assert_eq!(
+            event.previous_message.unwrap().text.as_deref(),
+            Some("Helo")
+        );
+    }
+
+    #[test]
+    fn unknown_subtypes_are_kept() {
+        let subtype: MessageSubtype = serde_json::from_str(r#""huddle_thread""#).unwrap();
+        assert_eq!(subtype, MessageSubtype::Other("huddle_thread".into()));
+        assert_eq!(subtype.to_string(), "huddle_thread");
+        assert_eq!(
+            MessageSubtype::from("bot_message"),
+            MessageSubtype::BotMessage
+        );
+    }
+}
@@ -0,0 +1,52 @@
+//! Events API payloads.
+//!
+//! Slack posts an `Envelope` to the app's request URL: a `url_verification`
+//! handshake, an `event_callback` wrapping one `Event`, or an
+//! `app_rate_limited` notice. Verify the request with `slaq::verify` first,
+//! then deserialize the body:
+//!
+//! ```
+//! use slaq::events::{Envelope, Event};
+//!
+//! let body = r#"{
+//!     "type": "event_callback",
+//!     "team_id": "T123",
+//!     "api_app_id": "A123",
+//!     "event_id": "Ev123",
+//!     "event_time": 1515449522,
+//!     "authorizations": [{"team_id": "T123", "user_id": "U0BOT", "is_bot": true}],
+//!     "event": {
+//!         "type": "app_mention",
+//!         "user": "U123",
+//!         "text": "<@U0BOT> deploy",
+//!         "ts": "1515449522.000016",
+//!         "channel": "C123",
+//!         "event_ts": "1515449522.000016"
+//!     }
+//! }"#;
+//!
+//! match serde_json::from_str(body).unwrap() {
+//!     Envelope::UrlVerification(handshake) => { /* respond with handshake.challenge */ }
+//!     Envelope::EventCallback(callback) => match callback.event {
+//!         Event::AppMention(mention) => assert_eq!(mention.channel, "C123"),
+//!         Event::Message(message) => { /* ... */ }
+//!         other => println!("ignoring {}", other.kind()),
+//!     },
+//!     _ => {}
+//! }
+//! ```
+//!
+//! Events this crate doesn't model deserialize as `Event::Unknown` with the
+//! raw JSON, so new event types never break parsing.
+
+mod envelope;
+mod event;
+mod message;
+
+pub use envelope::{AppRateLimited, Authorization, Envelope, EventCallback, UrlVerification};
+pub use event::{
+    AppHomeOpenedEvent, AppMentionEvent, ChannelCreatedEvent, ChannelEvent, ChannelRenameEvent,
+    CreatedChannel, Event, EventUser, LinkSharedEvent, MemberChannelEvent, ReactionEvent,
+    ReactionItem, RenamedChannel, RevokedTokens, SharedLink, TeamJoinEvent, TokensRevokedEvent,
+};
+pub use message::{MessageEvent, MessageSubtype};
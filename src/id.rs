@@ -0,0 +1,325 @@
+//! Typed Slack identifiers and message timestamps.
+//!
+//! Slack IDs are strings, which makes it easy to pass a user ID where a
+//! channel ID is expected. `ChannelId`, `UserId`, `TeamId` and `EnterpriseId`
+//! keep them apart: builders take channels as `impl Into<ChannelId>` and
+//! users as `impl Into<UserId>`, which plain strings convert into as well.
+//! Message timestamps are `Ts`, in builders, responses and events alike.
+//!
+//! ```
+//! use slaq::api::chat::update::Update;
+//! use slaq::id::{ChannelId, Ts};
+//!
+//! let channel = ChannelId::new("C123");
+//! let ts: Ts = "1405894322.002768".parse().unwrap();
+//! let update = Update::new(channel, ts).text("edited");
+//! let update = Update::new("C123", ts).text("edited");
+//! ```
+//!
+//! Passing a user ID as a channel doesn't compile:
+//!
+//! ```compile_fail
+//! use slaq::api::chat::update::Update;
+//! use slaq::id::{Ts, UserId};
+//!
+//! let update = Update::new(UserId::new("U123"), Ts::new(1_405_894_322, 2768));
+//! ```
+//!
+//! `Ts` parses, orders and formats message timestamps like
+//! `"1405894322.002768"`, and converts to and from `SystemTime`.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+macro_rules! id_type {
+    ($($(#[$doc:meta])* $name:ident;)*) => {$(
+        $(#[$doc])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Wraps an ID received from Slack.
+            #[must_use]
+            pub fn new(id: impl Into<String>) -> Self {
+                Self(id.into())
+            }
+
+            /// The ID as a string.
+            #[must_use]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Returns the ID as a `String`.
+            #[must_use]
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                Self(id.to_owned())
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                Self(id)
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl From<&$name> for String {
+            fn from(id: &$name) -> Self {
+                id.0.clone()
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    )*};
+}
+
+id_type! {
+    /// A conversation ID: a public or private channel (`C...`, `G...`) or a
+    /// direct message (`D...`).
+    ChannelId;
+    /// A user ID, e.g. `U123` or `W123`.
+    UserId;
+    /// A workspace ID, e.g. `T123`.
+    TeamId;
+    /// An Enterprise Grid organization ID, e.g. `E123`.
+    EnterpriseId;
+}
+
+impl ChannelId {
+    /// Returns `true` for direct message IDs, which start with `D`.
+    #[must_use]
+    pub fn is_direct_message(&self) -> bool {
+        self.0.starts_with('D')
+    }
+}
+
+/// A message timestamp, e.g. `1405894322.002768`.
+///
+/// Slack uses timestamps as message IDs within a channel: seconds since the
+/// Unix epoch and a six-digit fraction. `Ts` orders by time and formats back
+/// to the same string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Ts {
+    secs: u64,
+    micros: u32,
+}
+
+/// A string that isn't a valid message timestamp.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid message timestamp: {0:?}")]
+pub struct TsParseError(pub String);
+
+impl Ts {
+    /// Creates a timestamp from seconds and microseconds since the Unix epoch.
+    ///
+    /// Microseconds over 999,999 carry into the seconds.
+    #[must_use]
+    pub fn new(secs: u64, micros: u32) -> Self {
+        Self {
+            secs: secs + u64::from(micros / 1_000_000),
+            micros: micros % 1_000_000,
+        }
+    }
+
+    /// The current time.
+    #[must_use]
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Whole seconds since the Unix epoch.
+    #[must_use]
+    pub fn secs(self) -> u64 {
+        self.secs
+    }
+
+    /// The microsecond part.
+    #[must_use]
+    pub fn micros(self) -> u32 {
+        self.micros
+    }
+}
+
+impl FromStr for Ts {
+    type Err = TsParseError;
+
+    /// Parses `secs.micros`; the fraction may be missing or shorter than six
+    /// digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || TsParseError(s.to_owned());
+        let (secs, fraction) = s.split_once('.').unwrap_or((s, ""));
+        if secs.is_empty()
+            || fraction.len() > 6
+            || !secs
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(err());
+        }
+        let secs = secs.parse().map_err(|_| err())?;
+        let micros = format!("{fraction:0<6}").parse().map_err(|_| err())?;
+        Ok(Self { secs, micros })
+    }
+}
+
+impl TryFrom<&str> for Ts {
+    type Error = TsParseError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Ts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:06}", self.secs, self.micros)
+    }
+}
+
+impl From<Ts> for String {
+    fn from(ts: Ts) -> Self {
+        ts.to_string()
+    }
+}
+
+impl From<&Ts> for String {
+    fn from(ts: &Ts) -> Self {
+        ts.to_string()
+    }
+}
+
+impl PartialEq<str> for Ts {
+    fn eq(&self, other: &str) -> bool {
+        other.parse() == Ok(*self)
+    }
+}
+
+impl PartialEq<&str> for Ts {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl From<Ts> for SystemTime {
+    fn from(ts: Ts) -> Self {
+        UNIX_EPOCH + Duration::new(ts.secs, ts.micros * 1000)
+    }
+}
+
+/// Times before the Unix epoch map to the epoch; precision beyond
+/// microseconds is truncated.
+impl From<SystemTime> for Ts {
+    fn from(time: SystemTime) -> Self {
+        let since = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        Self {
+            secs: since.as_secs(),
+            micros: since.subsec_micros(),
+        }
+    }
+}
+
+impl Serialize for Ts {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ts_parses_orders_and_formats() {
+        let a: Ts = "1405894322.002768".parse().unwrap();
+        let b: Ts = "1405894322.1".parse().unwrap();
+        assert_eq!((a.secs(), a.micros()), (1_405_894_322, 2768));
+        assert_eq!(b.to_string(), "1405894322.100000");
+        assert!(a < b);
+        assert_eq!(
+            "1405894322".parse::<Ts>().unwrap(),
+            Ts::new(1_405_894_322, 0)
+        );
+        for bad in ["", ".5", "1.1234567", "1.-2", "abc", "1.2.3"] {
+            assert!(bad.parse::<Ts>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn ts_round_trips_system_time_and_json() {
+        let ts = Ts::new(1_700_000_000, 1_000_001);
+        assert_eq!(ts.to_string(), "1700000001.000001");
+        let time = SystemTime::from(ts);
+        assert_eq!(Ts::from(time), ts);
+        assert_eq!(
+            serde_json::to_string(&ts).unwrap(),
+            r#""1700000001.000001""#
+        );
+        let back: Ts = serde_json::from_str(r#""1700000001.000001""#).unwrap();
+        assert_eq!(back, ts);
+    }
+
+    #[test]
+    fn ids_are_distinct_string_newtypes() {
+        let channel = ChannelId::new("D123");
+        assert!(channel.is_direct_message());
+        assert_eq!(channel, "D123");
+        assert_eq!(
+            serde_json::to_string(&UserId::from("U1")).unwrap(),
+            r#""U1""#
+        );
+        assert_eq!(String::from(&TeamId::new("T1")), "T1");
+    }
+}
@@ -4,6 +4,12 @@
 //! Disable default features for a build-only crate.
 pub mod api;
 pub mod blocks;
+pub mod events;
+pub mod id;
+pub mod mrkdwn;
+pub mod schedule;
+#[cfg(feature = "verify")]
+pub mod verify;
 
 /// Default Slack Web API base URL.
 pub const DEFAULT_BASE_URL: &str = "https://slack.com/api";
@@ -0,0 +1,382 @@
+//! Building Slack mrkdwn text safely.
+//!
+//! `Mrkdwn` is text that is already valid mrkdwn. Strings converted into it
+//! are escaped, so user-supplied text can't inject `<!channel>` mentions or
+//! break links; mentions, links, dates and formatting come from the helpers
+//! below. The `mrkdwn_text` setters of `PostMessage` and the other chat
+//! methods take `Mrkdwn`, escaping plain strings passed to them, while `text`
+//! sends strings as they are:
+//!
+//! ```
+//! use slaq::api::chat::post_message::PostMessage;
+//! use slaq::mrkdwn::{self, bold, user};
+//!
+//! let name = "<!channel> & friends";
+//! let text = user("U123") + " renamed the team to " + bold(name);
+//! assert_eq!(text.as_str(), "<@U123> renamed the team to *&lt;!channel&gt; &amp; friends*");
+//!
+//! let msg = PostMessage::new("C123").mrkdwn_text(text);
+//! let escaped = PostMessage::new("C123").mrkdwn_text(name);
+//! assert_eq!(escaped.text.as_deref(), Some("&lt;!channel&gt; &amp; friends"));
+//! ```
+//!
+//! Going the other way, [`Document::parse`] turns mrkdwn received from Slack
+//! into a tree of [`Node`]s, which can be inspected for mentions and links or
+//! rendered back to mrkdwn, plain text or `CommonMark`:
+//!
+//! ```
+//! use slaq::mrkdwn::Document;
+//!
+//! let doc = Document::parse("*Ping* <@U123|ana>, see <https://example.com|the docs>");
+//! assert_eq!(doc.user_ids(), ["U123"]);
+//! assert_eq!(doc.to_plain_text(), "Ping @ana, see the docs");
+//! assert_eq!(doc.to_commonmark(), "**Ping** @ana, see [the docs](<https://example.com>)");
+//! ```
+
+use std::fmt;
+use std::ops::{Add, AddAssign};
+
+use crate::blocks::{MrkdwnText, Text};
+
+mod parse;
+mod render;
+
+#[cfg(feature = "markdown")]
+pub(crate) use parse::parse_mentions;
+pub use parse::{Document, Node};
+pub(crate) use render::{Syntax, entity_text};
+
+/// Text that is valid Slack mrkdwn.
+///
+/// Converting a `&str` or `String` escapes it; use [`Mrkdwn::raw`] for text
+/// that is already mrkdwn.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Mrkdwn(String);
+
+impl Mrkdwn {
+    /// Creates empty text.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps text that is already mrkdwn, without escaping it.
+    #[must_use]
+    pub fn raw(mrkdwn: impl Into<String>) -> Self {
+        Self(mrkdwn.into())
+    }
+
+    /// Appends `part`, escaping it if it is a plain string.
+    #[must_use]
+    pub fn push(mut self, part: impl Into<Mrkdwn>) -> Self {
+        self += part;
+        self
+    }
+
+    /// The mrkdwn source.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns `true` if the text is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the mrkdwn source.
+    #[must_use]
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<&str> for Mrkdwn {
+    fn from(text: &str) -> Self {
+        Self(escape(text))
+    }
+}
+
+impl From<String> for Mrkdwn {
+    fn from(text: String) -> Self {
+        Self(escape(&text))
+    }
+}
+
+impl From<&String> for Mrkdwn {
+    fn from(text: &String) -> Self {
+        Self(escape(text))
+    }
+}
+
+impl From<Mrkdwn> for String {
+    fn from(text: Mrkdwn) -> Self {
+        text.0
+    }
+}
+
+impl From<Mrkdwn> for MrkdwnText {
+    fn from(text: Mrkdwn) -> Self {
+        MrkdwnText::new(text.0)
+    }
+}
+
+impl From<Mrkdwn> for Text {
+    fn from(text: Mrkdwn) -> Self {
+        Text::mrkdwn(text.0)
+    }
+}
+
+impl fmt::Display for Mrkdwn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<T: Into<Mrkdwn>> Add<T> for Mrkdwn {
+    type Output = Mrkdwn;
+
+    fn add(self, rhs: T) -> Mrkdwn {
+        self.push(rhs)
+    }
+}
+
+impl<T: Into<Mrkdwn>> AddAssign<T> for Mrkdwn {
+    fn add_assign(&mut self, rhs: T) {
+        self.0.push_str(&rhs.into().0);
+    }
+}
+
+impl<T: Into<Mrkdwn>> FromIterator<T> for Mrkdwn {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().fold(Mrkdwn::new(), Mrkdwn::push)
+    }
+}
+
+/// Escapes `&`, `<` and `>`, the characters mrkdwn reserves for entities.
+#[must_use]
+pub fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Wraps the non-whitespace core of `text` in `marker`, as mrkdwn requires
+/// markers to touch the text they format.
+pub(crate) fn wrap(text: &str, marker: &str) -> String {
+    let core = text.trim();
+    if core.is_empty() {
+        return text.to_owned();
+    }
+    let start = text.len() - text.trim_start().len();
+    let end = start + core.len();
+    format!("{}{marker}{core}{marker}{}", &text[..start], &text[end..])
+}
+
+/// `*bold*` text.
+#[must_use]
+pub fn bold(text: impl Into<Mrkdwn>) -> Mrkdwn {
+    Mrkdwn(wrap(&text.into().0, "*"))
+}
+
+/// `_italic_` text.
+#[must_use]
+pub fn italic(text: impl Into<Mrkdwn>) -> Mrkdwn {
+    Mrkdwn(wrap(&text.into().0, "_"))
+}
+
+/// `~strikethrough~` text.
+#[must_use]
+pub fn strike(text: impl Into<Mrkdwn>) -> Mrkdwn {
+    Mrkdwn(wrap(&text.into().0, "~"))
+}
+
+/// `` `inline code` ``. Backticks in `text` would end the span and are replaced
+/// with `ˋ` (U+02CB).
+#[must_use]
+pub fn code(text: &str) -> Mrkdwn {
+    Mrkdwn(wrap(&escape(&text.replace('`', "\u{2cb}")), "`"))
+}
+
+/// A ```` ``` ```` code block.
+#[must_use]
+pub fn code_block(text: &str) -> Mrkdwn {
+    Mrkdwn(format!(
+        "```\n{}\n```",
+        escape(&text.replace("```", "\u{2cb}\u{2cb}\u{2cb}"))
+    ))
+}
+
+/// A block quote, with every line prefixed by `>`.
+#[must_use]
+pub fn quote(text: impl Into<Mrkdwn>) -> Mrkdwn {
+    let text = text.into().0;
+    Mrkdwn(
+        text.split('\n')
+            .map(|line| format!("&gt; {line}"))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+/// Escapes a URL for use inside `<...>`: `|` would end the URL early.
+fn escape_url(url: &str) -> String {
+    escape(url).replace('|', "%7C")
+}
+
+/// A link to `url` showing `label`.
+#[must_use]
+pub fn link(url: &str, label: impl Into<Mrkdwn>) -> Mrkdwn {
+    Mrkdwn(format!("<{}|{}>", escape_url(url), label.into().0))
+}
+
+/// A link showing the URL itself.
+#[must_use]
+pub fn url(url: &str) -> Mrkdwn {
+    Mrkdwn(format!("<{}>", escape_url(url)))
+}
+
+/// A `mailto:` link showing the address.
+#[must_use]
+pub fn email(address: &str) -> Mrkdwn {
+    Mrkdwn(format!(
+        "<mailto:{}|{}>",
+        escape_url(address),
+        escape(address)
+    ))
+}
+
+/// Mentions a user by ID, e.g. `<@U123>`.
+#[must_use]
+pub fn user(user_id: &str) -> Mrkdwn {
+    Mrkdwn(format!("<@{}>", escape_url(user_id)))
+}
+
+/// Links a channel by ID, e.g. `<#C123>`.
+#[must_use]
+pub fn channel(channel_id: &str) -> Mrkdwn {
+    Mrkdwn(format!("<#{}>", escape_url(channel_id)))
+}
+
+/// Mentions a user group by ID, e.g. `<!subteam^S123>`.
+#[must_use]
+pub fn usergroup(usergroup_id: &str) -> Mrkdwn {
+    Mrkdwn(format!("<!subteam^{}>", escape_url(usergroup_id)))
+}
+
+/// Who a broadcast mention notifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Broadcast {
+    /// Active members of the channel: `<!here>`.
+    Here,
+    /// All members of the channel: `<!channel>`.
+    Channel,
+    /// Everyone in the workspace's general channel: `<!everyone>`.
+    Everyone,
+}
+
+impl Broadcast {
+    /// The broadcast's name, e.g. `here`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Broadcast::Here => "here",
+            Broadcast::Channel => "channel",
+            Broadcast::Everyone => "everyone",
+        }
+    }
+}
+
+/// A broadcast mention, e.g. `<!here>`.
+#[must_use]
+pub fn broadcast(range: Broadcast) -> Mrkdwn {
+    Mrkdwn(format!("<!{}>", range.as_str()))
+}
+
+/// A date shown in the reader's time zone, e.g. `<!date^1700000000^{date_short} at {time}|Nov 14>`.
+///
+/// `format` may use tokens like `{date_num}`, `{date}`, `{date_short}`,
+/// `{date_long}`, `{date_pretty}`, `{date_short_pretty}`, `{date_long_pretty}`,
+/// `{time}`, `{time_secs}` and `{ago}`. `fallback` is shown by clients that
+/// can't format dates.
+#[must_use]
+pub fn date(timestamp: i64, format: &str, fallback: &str) -> Mrkdwn {
+    Mrkdwn(format!(
+        "<!date^{timestamp}^{}|{}>",
+        escape_url(format),
+        escape(fallback)
+    ))
+}
+
+/// Like [`date`], linking the formatted date to `url`.
+#[must_use]
+pub fn date_link(timestamp: i64, format: &str, url: &str, fallback: &str) -> Mrkdwn {
+    Mrkdwn(format!(
+        "<!date^{timestamp}^{}^{}|{}>",
+        escape_url(format),
+        escape_url(url),
+        escape(fallback)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped_and_raw_is_not() {
+        assert_eq!(
+            Mrkdwn::from("a < b && <!here>").as_str(),
+            "a &lt; b &amp;&amp; &lt;!here&gt;"
+        );
+        assert_eq!(Mrkdwn::raw("<!here>").as_str(), "<!here>");
+    }
+
+    #[test]
+    fn formatting_wraps_trimmed_text() {
+        assert_eq!(bold(" hi ").as_str(), " *hi* ");
+        assert_eq!(italic(bold("x")).as_str(), "_*x*_");
+        assert_eq!(strike("old").as_str(), "~old~");
+        assert_eq!(code("a<`b`>").as_str(), "`a&lt;\u{2cb}b\u{2cb}&gt;`");
+        assert_eq!(code_block("x < y").as_str(), "```\nx &lt; y\n```");
+        assert_eq!(quote("one\ntwo").as_str(), "&gt; one\n&gt; two");
+    }
+
+    #[test]
+    fn mentions_links_and_dates() {
+        assert_eq!(user("U1").as_str(), "<@U1>");
+        assert_eq!(channel("C1").as_str(), "<#C1>");
+        assert_eq!(usergroup("S1").as_str(), "<!subteam^S1>");
+        assert_eq!(broadcast(Broadcast::Here).as_str(), "<!here>");
+        assert_eq!(
+            link("https://x.test/?a=1&b=|", "Tom & Jerry").as_str(),
+            "<https://x.test/?a=1&amp;b=%7C|Tom &amp; Jerry>"
+        );
+        assert_eq!(url("https://x.test").as_str(), "<https://x.test>");
+        assert_eq!(
+            date(1_700_000_000, "{date_short} at {time}", "Nov 14").as_str(),
+            "<!date^1700000000^{date_short} at {time}|Nov 14>"
+        );
+        assert_eq!(
+            date_link(1, "{ago}", "https://x.test", "then").as_str(),
+            "<!date^1^{ago}^https://x.test|then>"
+        );
+    }
+
+    #[test]
+    fn parts_concatenate() {
+        let text: Mrkdwn = ["a", "<b>"].into_iter().collect();
+        assert_eq!(text.as_str(), "a&lt;b&gt;");
+        let mut text = user("U1") + " said " + italic("hi");
+        text += "!";
+        assert_eq!(text.to_string(), "<@U1> said _hi_!");
+    }
+}
@@ -0,0 +1,556 @@
+//! Parsing Slack mrkdwn into a typed AST.
+
+use super::Broadcast;
+
+/// A node of parsed mrkdwn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// Plain text, with `&amp;`, `&lt;` and `&gt;` decoded.
+    Text(String),
+    /// `*bold*` content.
+    Bold(Vec<Node>),
+    /// `_italic_` content.
+    Italic(Vec<Node>),
+    /// `~strikethrough~` content.
+    Strike(Vec<Node>),
+    /// `` `inline code` ``, decoded.
+    Code(String),
+    /// A ```` ``` ```` code block, decoded.
+    Preformatted(String),
+    /// A block quote of one or more lines.
+    Quote(Vec<Node>),
+    /// A line break.
+    LineBreak,
+    /// `<url>` or `<url|label>`.
+    Link { url: String, label: Option<String> },
+    /// `<@U123>` or `<@U123|name>`.
+    User { id: String, label: Option<String> },
+    /// `<#C123>` or `<#C123|name>`.
+    Channel { id: String, label: Option<String> },
+    /// `<!subteam^S123>` or `<!subteam^S123|@team>`.
+    Usergroup { id: String, label: Option<String> },
+    /// `<!here>`, `<!channel>` or `<!everyone>`.
+    Broadcast {
+        range: Broadcast,
+        label: Option<String>,
+    },
+    /// `<!date^timestamp^format^url|fallback>`, with the URL optional.
+    Date {
+        timestamp: i64,
+        format: String,
+        url: Option<String>,
+        fallback: String,
+    },
+    /// Any other `<!command>`, keeping everything after the `!`.
+    Command {
+        command: String,
+        label: Option<String>,
+    },
+    /// An `:emoji:` code, by name.
+    Emoji(String),
+}
+
+/// Parsed mrkdwn text.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Document {
+    /// Top-level nodes in order.
+    pub nodes: Vec<Node>,
+}
+
+impl Document {
+    /// Parses mrkdwn, as found in the `text` of messages and events.
+    ///
+    /// Parsing never fails: markup that doesn't form valid mrkdwn, such as an
+    /// unclosed `*`, is kept as text.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        Self {
+            nodes: parse_blocks(text),
+        }
+    }
+
+    /// Iterates over all nodes depth-first, parents before their children.
+    pub fn iter(&self) -> impl Iterator<Item = &Node> {
+        let mut stack: Vec<&Node> = self.nodes.iter().rev().collect();
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            if let Node::Bold(children)
+            | Node::Italic(children)
+            | Node::Strike(children)
+            | Node::Quote(children) = node
+            {
+                stack.extend(children.iter().rev());
+            }
+            Some(node)
+        })
+    }
+
+    /// IDs of mentioned users, in order of appearance.
+    #[must_use]
+    pub fn user_ids(&self) -> Vec<&str> {
+        self.iter()
+            .filter_map(|node| match node {
+                Node::User { id, .. } => Some(id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// IDs of linked channels, in order of appearance.
+    #[must_use]
+    pub fn channel_ids(&self) -> Vec<&str> {
+        self.iter()
+            .filter_map(|node| match node {
+                Node::Channel { id, .. } => Some(id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// IDs of mentioned user groups, in order of appearance.
+    #[must_use]
+    pub fn usergroup_ids(&self) -> Vec<&str> {
+        self.iter()
+            .filter_map(|node| match node {
+                Node::Usergroup { id, .. } => Some(id.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// URLs of links, in order of appearance.
+    #[must_use]
+    pub fn links(&self) -> Vec<&str> {
+        self.iter()
+            .filter_map(|node| match node {
+                Node::Link { url, .. } => Some(url.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Broadcast mentions, in order of appearance.
+    #[must_use]
+    pub fn broadcasts(&self) -> Vec<Broadcast> {
+        self.iter()
+            .filter_map(|node| match node {
+                Node::Broadcast { range, .. } => Some(*range),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+fn quote_line(line: &str) -> Option<&str> {
+    let rest = line
+        .strip_prefix("&gt;")
+        .or_else(|| line.strip_prefix('>'))?;
+    Some(rest.strip_prefix(' ').unwrap_or(rest))
+}
+
+fn parse_blocks(text: &str) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut lines = text.split('\n').peekable();
+    // Whether the last node ended a line of text, so the next line needs a break.
+    let mut open_line = false;
+    while let Some(line) = lines.next() {
+        if let Some(rest) = line.strip_prefix("```") {
+            let mut code = String::new();
+            let mut body = rest;
+            let mut after = "";
+            loop {
+                if let Some(end) = body.find("```") {
+                    code.push_str(&body[..end]);
+                    after = &body[end + 3..];
+                    break;
+                }
+                code.push_str(body);
+                match lines.next() {
+                    Some(next) => {
+                        code.push('\n');
+                        body = next;
+                    }
+                    None => break,
+                }
+            }
+            let code = code.strip_prefix('\n').unwrap_or(&code);
+            let code = code.strip_suffix('\n').unwrap_or(code);
+            nodes.push(Node::Preformatted(unescape(code)));
+            open_line = false;
+            if !after.is_empty() {
+                nodes.extend(parse_inline(after));
+                open_line = true;
+            }
+            continue;
+        }
+        if let Some(quoted) = quote_line(line) {
+            let mut children = parse_inline(quoted);
+            while let Some(next) = lines.peek().and_then(|l| quote_line(l)) {
+                children.push(Node::LineBreak);
+                children.extend(parse_inline(next));
+                lines.next();
+            }
+            nodes.push(Node::Quote(children));
+            open_line = false;
+            continue;
+        }
+        if open_line {
+            nodes.push(Node::LineBreak);
+        }
+        nodes.extend(parse_inline(line));
+        open_line = true;
+    }
+    merge_text(nodes)
+}
+
+/// Parses one line of mrkdwn.
+fn parse_inline(text: &str) -> Vec<Node> {
+    inline(text, Markup::Mrkdwn)
+}
+
+/// Splits Slack mentions such as `<@U123>` and `<!here>`, `<!date^..>`
+/// tokens and `:emoji:` codes out of text that isn't otherwise mrkdwn, such
+/// as text read from `CommonMark`. Other text, including `<...>` that isn't
+/// a mention, is kept as is.
+#[cfg(feature = "markdown")]
+pub(crate) fn parse_mentions(text: &str) -> Vec<Node> {
+    inline(text, Markup::Mentions)
+}
+
+/// What [`inline`] recognizes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Markup {
+    /// All of mrkdwn, with `&amp;`, `&lt;` and `&gt;` decoded.
+    Mrkdwn,
+    /// Only mentions, commands and emoji codes, in undecoded text.
+    #[cfg_attr(not(feature = "markdown"), allow(dead_code))]
+    Mentions,
+}
+
+fn inline(text: &str, markup: Markup) -> Vec<Node> {
+    let mrkdwn = markup == Markup::Mrkdwn;
+    let decode = |text: &str| {
+        if mrkdwn {
+            unescape(text)
+        } else {
+            text.to_owned()
+        }
+    };
+    let chars: Vec<char> = text.chars().collect();
+    // The scans for `>` and closing markers are lookups, so a line full of
+    // unclosed `<` or `*` parses in linear time.
+    let next_gt = next_positions(chars.len(), |i| chars[i] == '>');
+    let closers = MARKERS.map(|marker| {
+        if mrkdwn {
+            next_positions(chars.len(), |end| closes(&chars, end, marker))
+        } else {
+            Vec::new()
+        }
+    });
+    let closing_marker = |open: usize| {
+        let marker = MARKERS.iter().position(|&m| m == chars[open])?;
+        closing_marker(&chars, open, &closers[marker])
+    };
+    let mut nodes = Vec::new();
+    let mut plain = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let parsed = match c {
+            '<' => next_gt[i]
+                .filter(|_| mrkdwn || matches!(chars.get(i + 1), Some('@' | '#' | '!')))
+                .map(|end| {
+                    let inner: String = chars[i + 1..end].iter().collect();
+                    (entity(&inner), end + 1)
+                }),
+            '`' | '*' | '_' | '~' if !mrkdwn => None,
+            '`' => closing_marker(i).map(|end| {
+                let inner: String = chars[i + 1..end].iter().collect();
+                (Node::Code(unescape(&inner)), end + 1)
+            }),
+            '*' | '_' | '~' => closing_marker(i).map(|end| {
+                let inner: String = chars[i + 1..end].iter().collect();
+                let children = parse_inline(&inner);
+                let node = match c {
+                    '*' => Node::Bold(children),
+                    '_' => Node::Italic(children),
+                    _ => Node::Strike(children),
+                };
+                (node, end + 1)
+            }),
+            ':' => emoji(&chars, i),
+            _ => None,
+        };
+        if let Some((node, next)) = parsed {
+            if !plain.is_empty() {
+                nodes.push(Node::Text(decode(&std::mem::take(&mut plain))));
+            }
+            nodes.push(node);
+            i = next;
+        } else {
+            plain.push(c);
+            i += 1;
+        }
+    }
+    if !plain.is_empty() {
+        nodes.push(Node::Text(decode(&plain)));
+    }
+    nodes
+}
+
+/// Formatting markers, in the order of their closer tables in [`inline`].
+const MARKERS: [char; 4] = ['`', '*', '_', '~'];
+
+/// For each position up to `len`, the first position at or after it that
+/// satisfies `is_match`.
+fn next_positions(len: usize, is_match: impl Fn(usize) -> bool) -> Vec<Option<usize>> {
+    let mut next = vec![None; len + 1];
+    for i in (0..len).rev() {
+        next[i] = if is_match(i) { Some(i) } else { next[i + 1] };
+    }
+    next
+}
+
+fn is_boundary(c: Option<&char>) -> bool {
+    c.is_none_or(|c| !c.is_alphanumeric())
+}
+
+/// Whether `marker` at `end` can close a span: it touches the text before it
+/// and sits at a word boundary.
+fn closes(chars: &[char], end: usize, marker: char) -> bool {
+    chars[end] == marker
+        && end > 0
+        && !chars[end - 1].is_whitespace()
+        && is_boundary(chars.get(end + 1))
+}
+
+/// Finds the marker closing the one at `open`, given the positions of the
+/// next closer of its kind. Markers must sit at word boundaries and touch
+/// the text they format, and can't span lines.
+fn closing_marker(chars: &[char], open: usize, closers: &[Option<usize>]) -> Option<usize> {
+    if !is_boundary(open.checked_sub(1).and_then(|p| chars.get(p)))
+        || chars.get(open + 1).is_none_or(|c| c.is_whitespace())
+    {
+        return None;
+    }
+    closers.get(open + 2).copied().flatten()
+}
+
+/// Parses an `:emoji:` code at `open`, unless glued to a preceding word as in `10:30:00`.
+fn emoji(chars: &[char], open: usize) -> Option<(Node, usize)> {
+    if open > 0 && chars[open - 1].is_alphanumeric() {
+        return None;
+    }
+    let len = chars[open + 1..]
+        .iter()
+        .position(|c| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '+' | '-' | '\'')))?;
+    let name: String = chars[open + 1..open + 1 + len].iter().collect();
+    (len > 0 && chars[open + 1 + len] == ':' && name.bytes().any(|b| b.is_ascii_lowercase()))
+        .then(|| (Node::Emoji(name), open + len + 2))
+}
+
+/// Parses the inside of a `<...>` entity.
+fn entity(inner: &str) -> Node {
+    let (body, label) = inner
+        .split_once('|')
+        .map_or((inner, None), |(body, label)| (body, Some(unescape(label))));
+    if let Some(id) = body.strip_prefix('@') {
+        return Node::User {
+            id: id.to_owned(),
+            label,
+        };
+    }
+    if let Some(id) = body.strip_prefix('#') {
+        return Node::Channel {
+            id: id.to_owned(),
+            label,
+        };
+    }
+    let Some(command) = body.strip_prefix('!') else {
+        return Node::Link {
+            url: unescape(body),
+            label,
+        };
+    };
+    let range = match command {
+        "here" => Some(Broadcast::Here),
+        "channel" => Some(Broadcast::Channel),
+        "everyone" => Some(Broadcast::Everyone),
+        _ => None,
+    };
+    if let Some(range) = range {
+        return Node::Broadcast { range, label };
+    }
+    if let Some(id) = command.strip_prefix("subteam^") {
+        return Node::Usergroup {
+            id: id.to_owned(),
+            label,
+        };
+    }
+    if let Some(date) = command.strip_prefix("date^") {
+        let mut parts = date.splitn(3, '^');
+        if let (Some(Ok(timestamp)), Some(format)) = (parts.next().map(str::parse), parts.next()) {
+            return Node::Date {
+                timestamp,
+                format: unescape(format),
+                url: parts.next().map(unescape),
+                fallback: label.unwrap_or_default(),
+            };
+        }
+    }
+    Node::Command {
+        command: unescape(command),
+        label,
+    }
+}
+
+/// Joins adjacent text nodes left apart by failed markup.
+fn merge_text(nodes: Vec<Node>) -> Vec<Node> {
+    let mut out: Vec<Node> = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        if let (Some(Node::Text(last)), Node::Text(text)) = (out.last_mut(), &node) {
+            last.push_str(text);
+        } else {
+            out.push(node);
+        }
+    }
+    out
+}
+
+/// Decodes the escapes Slack uses in mrkdwn.
+pub(crate) fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Node {
+        Node::Text(s.to_owned())
+    }
+
+    #[test]
+    fn parses_entities() {
+        assert_eq!(
+            parse_blocks(
+                "<@U1> <#C1|general> <!subteam^S1|@ops> <!here> <https://x.test?a=1&amp;b=2|docs> <!foo^bar>"
+            ),
+            vec![
+                Node::User {
+                    id: "U1".into(),
+                    label: None
+                },
+                text(" "),
+                Node::Channel {
+                    id: "C1".into(),
+                    label: Some("general".into())
+                },
+                text(" "),
+                Node::Usergroup {
+                    id: "S1".into(),
+                    label: Some("@ops".into())
+                },
+                text(" "),
+                Node::Broadcast {
+                    range: Broadcast::Here,
+                    label: None
+                },
+                text(" "),
+                Node::Link {
+                    url: "https://x.test?a=1&b=2".into(),
+                    label: Some("docs".into())
+                },
+                text(" "),
+                Node::Command {
+                    command: "foo^bar".into(),
+                    label: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(
+            parse_blocks("<!date^1700000000^{date_short}^https://x.test|Nov 14>"),
+            vec![Node::Date {
+                timestamp: 1_700_000_000,
+                format: "{date_short}".into(),
+                url: Some("https://x.test".into()),
+                fallback: "Nov 14".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_nested_formatting_and_ignores_intraword_markers() {
+        assert_eq!(
+            parse_blocks("*bold _both_* ~gone~ `a*b*` snake_case_name 2 * 3 :tada:"),
+            vec![
+                Node::Bold(vec![text("bold "), Node::Italic(vec![text("both")])]),
+                text(" "),
+                Node::Strike(vec![text("gone")]),
+                text(" "),
+                Node::Code("a*b*".into()),
+                text(" snake_case_name 2 * 3 "),
+                Node::Emoji("tada".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_code_blocks_quotes_and_breaks() {
+        assert_eq!(
+            parse_blocks("Logs:\n```\nx &lt; 1\n```\n&gt; quoted\n&gt; *twice*\nafter"),
+            vec![
+                text("Logs:"),
+                Node::Preformatted("x < 1".into()),
+                Node::Quote(vec![
+                    text("quoted"),
+                    Node::LineBreak,
+                    Node::Bold(vec![text("twice")]),
+                ]),
+                text("after"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn mentions_only_leaves_other_markup() {
+        assert_eq!(
+            parse_mentions("*hi* <@U1> &amp; <b> :tada:"),
+            vec![
+                text("*hi* "),
+                Node::User {
+                    id: "U1".into(),
+                    label: None
+                },
+                text(" &amp; <b> "),
+                Node::Emoji("tada".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn document_extracts_mentions_and_links() {
+        let doc =
+            Document::parse("*<@U1>* and <@U2>, see <#C1> and <https://x.test>\n&gt; <!channel>");
+        assert_eq!(doc.user_ids(), ["U1", "U2"]);
+        assert_eq!(doc.channel_ids(), ["C1"]);
+        assert_eq!(doc.links(), ["https://x.test"]);
+        assert_eq!(doc.broadcasts(), [Broadcast::Channel]);
+        assert!(doc.usergroup_ids().is_empty());
+    }
+
+    #[test]
+    fn unclosed_markup_parses_in_linear_time() {
+        for c in ['<', '*', '_', '`'] {
+            let line = format!("{c}a ").repeat(40_000);
+            assert_eq!(parse_blocks(&line), vec![text(&line)]);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Rendering parsed mrkdwn back to mrkdwn, plain text and `CommonMark`.
+
+use super::parse::{Document, Node};
+use super::{escape, escape_url};
+
+impl Document {
+    /// Renders the document as mrkdwn.
+    ///
+    /// Text parsed from canonical mrkdwn renders back unchanged.
+    #[must_use]
+    pub fn to_mrkdwn(&self) -> String {
+        render_blocks(&self.nodes, &Mrkdwn)
+    }
+
+    /// Renders the document as plain text without markup, showing link
+    /// labels, `@name` and `#name` for mentions and date fallbacks.
+    #[must_use]
+    pub fn to_plain_text(&self) -> String {
+        render_blocks(&self.nodes, &Plain)
+    }
+
+    /// Renders the document as `CommonMark`. Mentions become `@name` and
+    /// `#name` text, as `CommonMark` has no equivalent.
+    #[must_use]
+    pub fn to_commonmark(&self) -> String {
+        render_blocks(&self.nodes, &CommonMark)
+    }
+
+    /// Renders the document with the markup of `syntax`.
+    pub(crate) fn render(&self, syntax: &dyn Syntax) -> String {
+        render_blocks(&self.nodes, syntax)
+    }
+}
+
+/// Markup for one output format.
+pub(crate) trait Syntax {
+    fn text(&self, text: &str) -> String;
+    fn styled(&self, node: &Node, inner: String) -> String;
+    fn code(&self, code: &str) -> String;
+    fn pre(&self, code: &str) -> String;
+    fn quote(&self, inner: &str) -> String;
+    fn line_break(&self) -> &'static str;
+    /// Separator placed around code blocks and quotes.
+    fn block_gap(&self) -> &'static str;
+    fn entity(&self, node: &Node) -> String;
+}
+
+fn render_blocks(nodes: &[Node], syntax: &dyn Syntax) -> String {
+    let mut out = String::new();
+    let mut after_block = false;
+    for node in nodes {
+        let rendered = match node {
+            Node::Preformatted(code) => syntax.pre(code),
+            Node::Quote(children) => syntax.quote(&render_inline(children, syntax)),
+            node => {
+                if after_block {
+                    out.push_str(syntax.block_gap());
+                    after_block = false;
+                }
+                out.push_str(&render_node(node, syntax));
+                continue;
+            }
+        };
+        if !out.is_empty() {
+            if let Some(trimmed) = out.strip_suffix(syntax.line_break()) {
+                out.truncate(trimmed.len());
+            }
+            out.push_str(syntax.block_gap());
+        }
+        out.push_str(&rendered);
+        after_block = true;
+    }
+    out
+}
+
+fn render_inline(nodes: &[Node], syntax: &dyn Syntax) -> String {
+    nodes.iter().map(|node| render_node(node, syntax)).collect()
+}
+
+fn render_node(node: &Node, syntax: &dyn Syntax) -> String {
+    match node {
+        Node::Text(text) => syntax.text(text),
+        Node::Bold(children) | Node::Italic(children) | Node::Strike(children) => {
+            syntax.styled(node, render_inline(children, syntax))
+        }
+        Node::Code(code) => syntax.code(code),
+        Node::Preformatted(code) => syntax.pre(code),
+        Node::Quote(children) => syntax.quote(&render_inline(children, syntax)),
+        Node::LineBreak => syntax.line_break().to_owned(),
+        node => syntax.entity(node),
+    }
+}
+
+/// Readable text for mentions and other entities, shared by the plain text,
+/// `CommonMark` and HTML renderers.
+pub(crate) fn entity_text(node: &Node) -> String {
+    match node {
+        Node::Link { url, label } => label.clone().unwrap_or_else(|| url.clone()),
+        Node::User { id, label } | Node::Usergroup { id, label } => match label {
+            Some(label) if label.starts_with('@') => label.clone(),
+            Some(label) => format!("@{label}"),
+            None => format!("@{id}"),
+        },
+        Node::Channel { id, label } => format!("#{}", label.as_deref().unwrap_or(id)),
+        Node::Broadcast { range, label } => label
+            .clone()
+            .unwrap_or_else(|| format!("@{}", range.as_str())),
+        Node::Date { fallback, .. } => fallback.clone(),
+        Node::Command { command, label } => label
+            .clone()
+            .unwrap_or_else(|| format!("@{}", command.split('^').next().unwrap_or(command))),
+        Node::Emoji(name) => format!(":{name}:"),
+        _ => String::new(),
+    }
+}
+
+struct Mrkdwn;
+
+impl Syntax for Mrkdwn {
+    fn text(&self, text: &str) -> String {
+        escape(text)
+    }
+
+    fn styled(&self, node: &Node, inner: String) -> String {
+        let marker = match node {
+            Node::Bold(_) => '*',
+            Node::Italic(_) => '_',
+            _ => '~',
+        };
+        format!("{marker}{inner}{marker}")
+    }
+
+    fn code(&self, code: &str) -> String {
+        format!("`{}`", escape(code))
+    }
+
+    fn pre(&self, code: &str) -> String {
+        format!("```\n{}\n```", escape(code))
+    }
+
+    fn quote(&self, inner: &str) -> String {
+        inner
+            .split('\n')
+            .map(|line| format!("&gt; {line}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn line_break(&self) -> &'static str {
+        "\n"
+    }
+
+    fn block_gap(&self) -> &'static str {
+        "\n"
+    }
+
+    fn entity(&self, node: &Node) -> String {
+        let label = |label: &Option<String>| {
+            label
+                .as_deref()
+                .map(|l| format!("|{}", escape(l)))
+                .unwrap_or_default()
+        };
+        match node {
+            Node::Link { url, label: l } => format!("<{}{}>", escape_url(url), label(l)),
+            Node::User { id, label: l } => format!("<@{id}{}>", label(l)),
+            Node::Channel { id, label: l } => format!("<#{id}{}>", label(l)),
+            Node::Usergroup { id, label: l } => format!("<!subteam^{id}{}>", label(l)),
+            Node::Broadcast { range, label: l } => format!("<!{}{}>", range.as_str(), label(l)),
+            Node::Date {
+                timestamp,
+                format,
+                url,
+                fallback,
+            } => {
+                let url = url
+                    .as_deref()
+                    .map(|u| format!("^{}", escape_url(u)))
+                    .unwrap_or_default();
+                format!(
+                    "<!date^{timestamp}^{}{url}|{}>",
+                    escape_url(format),
+                    escape(fallback)
+                )
+            }
+            Node::Command { command, label: l } => format!("<!{}{}>", escape(command), label(l)),
+            Node::Emoji(name) => format!(":{name}:"),
+            _ => String::new(),
+        }
+    }
+}
+
+struct Plain;
+
+impl Syntax for Plain {
+    fn text(&self, text: &str) -> String {
+        text.to_owned()
+    }
+
+    fn styled(&self, _node: &Node, inner: String) -> String {
+        inner
+    }
+
+    fn code(&self, code: &str) -> String {
+        code.to_owned()
+    }
+
+    fn pre(&self, code: &str) -> String {
+        code.to_owned()
+    }
+
+    fn quote(&self, inner: &str) -> String {
+        inner.to_owned()
+    }
+
+    fn line_break(&self) -> &'static str {
+        "\n"
+    }
+
+    fn block_gap(&self) -> &'static str {
+        "\n"
+    }
+
+    fn entity(&self, node: &Node) -> String {
+        entity_text(node)
+    }
+}
+
+struct CommonMark;
+
+impl CommonMark {
+    /// Backslash-escapes characters `CommonMark` would read as markup. `#`,
+    /// `-` and `+` only matter at the start of a line, which `line_start`
+    /// says `text` may begin.
+    fn escape(text: &str, line_start: bool) -> String {
+        let mut out = String::with_capacity(text.len());
+        for c in text.chars() {
+            let line_start = if out.is_empty() {
+                line_start
+            } else {
+                out.ends_with('\n')
+            };
+            if matches!(
+                c,
+                '\\' | '`' | '*' | '_' | '~' | '[' | ']' | '<' | '>' | '&'
+            ) || (line_start && matches!(c, '#' | '-' | '+'))
+            {
+                out.push('\\');
+            }
+            out.push(c);
+        }
+        out
+    }
+}
+
+impl Syntax for CommonMark {
+    fn text(&self, text: &str) -> String {
+        Self::escape(text, true)
+    }
+
+    fn styled(&self, node: &Node, inner: String) -> String {
+        let marker = match node {
+            Node::Bold(_) => "**",
+            Node::Italic(_) => "_",
+            _ => "~~",
+        };
+        format!("{marker}{inner}{marker}")
+    }
+
+    fn code(&self, code: &str) -> String {
+        // Use a fence longer than any backtick run inside the code.
+        let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest + 1);
+        if code.starts_with('`') || code.ends_with('`') {
+            format!("{fence} {code} {fence}")
+        } else {
+            format!("{fence}{code}{fence}")
+        }
+    }
+
+    fn pre(&self, code: &str) -> String {
+        let longest = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+        let fence = "`".repeat(longest.max(2) + 1);
+        format!("{fence}\n{code}\n{fence}")
+    }
+
+    fn quote(&self, inner: &str) -> String {
+        inner
+            .split('\n')
+            .map(|line| format!("> {line}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn line_break(&self) -> &'static str {
+        "\\\n"
+    }
+
+    fn block_gap(&self) -> &'static str {
+        "\n\n"
+    }
+
+    fn entity(&self, node: &Node) -> String {
+        match node {
+            Node::Link {
+                url,
+                label: Some(label),
+            } => format!("[{}](<{url}>)", Self::escape(label, false)),
+            Node::Link { url, label: None } => format!("<{url}>"),
+            Node::Date {
+                url: Some(url),
+                fallback,
+                ..
+            } => format!("[{}](<{url}>)", Self::escape(fallback, false)),
+            node => Self::escape(&entity_text(node), false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "*Deploy* of `api` by <@U1|ana> in <#C1>: <https://x.test?a=1&amp;b=2|logs> :tada:\n\
+                          ```\nx &lt; 1\n```\n\
+                          &gt; _quoted_ ~old~\n&gt; <!here>\n\
+                          <!date^1700000000^{date_short}|Nov 14> <!subteam^S1>";
+
+    #[test]
+    fn mrkdwn_round_trips() {
+        assert_eq!(Document::parse(SOURCE).to_mrkdwn(), SOURCE);
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        assert_eq!(
+            Document::parse(SOURCE).to_plain_text(),
+            "Deploy of api by @ana in #C1: logs :tada:\n\
+             x < 1\n\
+             quoted old\n@here\n\
+             Nov 14 @S1"
+        );
+    }
+
+    #[test]
+    fn renders_commonmark() {
+        assert_eq!(
+            Document::parse(SOURCE).to_commonmark(),
+            "**Deploy** of `api` by @ana in #C1: [logs](<https://x.test?a=1&b=2>) :tada:\n\n\
+             ```\nx < 1\n```\n\n\
+             > _quoted_ ~~old~~\\\n> @here\n\n\
+             Nov 14 @S1"
+        );
+        assert_eq!(
+            Document::parse("# not *a* [heading]").to_commonmark(),
+            "\\# not **a** \\[heading\\]"
+        );
+    }
+}
@@ -0,0 +1,326 @@
+//! Cron expressions for recurring messages.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use super::PostAt;
+
+const DAY: i64 = 86_400;
+
+/// How many days ahead to search for a match before giving up, enough to
+/// reach the next February 29th.
+const SEARCH_DAYS: i64 = 8 * 366;
+
+/// A five-field cron expression: minute, hour, day of month, month and day
+/// of week.
+///
+/// Fields take `*`, numbers, ranges `a-b`, steps `*/n` and `a-b/n`, and
+/// comma-separated lists. Months and weekdays also take three-letter names,
+/// and Sunday is `0` or `7`. `@hourly`, `@daily`, `@weekly`, `@monthly` and
+/// `@yearly` are accepted as shorthands. As in classic cron, when both day
+/// fields are restricted a day matching either one matches.
+///
+/// ```
+/// use slaq::schedule::{Cron, PostAt};
+///
+/// let standup: Cron = "30 9 * * mon-fri".parse().unwrap();
+/// // Friday 2024-03-01 12:00 UTC.
+/// let friday = PostAt::from_unix(1_709_294_400);
+/// let next = standup.next_after(friday, 0).unwrap();
+/// assert_eq!(next.unix(), 1_709_544_600); // Monday 2024-03-04 09:30 UTC
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cron {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+/// A cron expression that can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CronError {
+    #[error("expected 5 fields, found {0}")]
+    FieldCount(usize),
+    #[error("invalid {field} field: {value:?}")]
+    InvalidField { field: &'static str, value: String },
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const FIELDS: [Field; 5] = [
+    Field {
+        name: "minute",
+        min: 0,
+        max: 59,
+        names: &[],
+    },
+    Field {
+        name: "hour",
+        min: 0,
+        max: 23,
+        names: &[],
+    },
+    Field {
+        name: "day of month",
+        min: 1,
+        max: 31,
+        names: &[],
+    },
+    Field {
+        name: "month",
+        min: 1,
+        max: 12,
+        names: &[
+            "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+        ],
+    },
+    Field {
+        name: "day of week",
+        min: 0,
+        max: 7,
+        names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+    },
+];
+
+impl Field {
+    fn parse(&self, value: &str) -> Result<u64, CronError> {
+        let err = || CronError::InvalidField {
+            field: self.name,
+            value: value.to_owned(),
+        };
+        let mut bits = 0;
+        for part in value.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    (range, step.parse().ok().filter(|&s| s > 0).ok_or_else(err)?)
+                }
+                None => (part, 1),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (
+                    self.number(start).ok_or_else(err)?,
+                    self.number(end).ok_or_else(err)?,
+                )
+            } else {
+                let start = self.number(range).ok_or_else(err)?;
+                // `5/15` means every 15 from 5 to the end of the range.
+                (start, if part.contains('/') { self.max } else { start })
+            };
+            if start > end {
+                return Err(err());
+            }
+            for n in (start..=end).step_by(step) {
+                bits |= 1 << n;
+            }
+        }
+        Ok(bits)
+    }
+
+    fn number(&self, value: &str) -> Option<u32> {
+        let n = match self
+            .names
+            .iter()
+            .position(|name| value.eq_ignore_ascii_case(name))
+        {
+            Some(i) => u32::try_from(i).ok()? + self.min,
+            None => value.parse().ok()?,
+        };
+        (self.min..=self.max).contains(&n).then_some(n)
+    }
+}
+
+impl Cron {
+    /// The first time after `after` that matches, evaluated in the time zone
+    /// `utc_offset` seconds from UTC, such as a user's `tz_offset`.
+    ///
+    /// Returns `None` if the expression never matches, like `0 0 30 2 *`.
+    #[must_use]
+    pub fn next_after(&self, after: PostAt, utc_offset: i32) -> Option<PostAt> {
+        let offset = i64::from(utc_offset);
+        // The first whole minute strictly after `after`.
+        let start = (after.unix() + offset).div_euclid(60) * 60 + 60;
+        let first_day = start.div_euclid(DAY);
+        for day in first_day..first_day + SEARCH_DAYS {
+            if !self.matches_day(day) {
+                continue;
+            }
+            let from = if day == first_day {
+                start.rem_euclid(DAY) / 60
+            } else {
+                0
+            };
+            for minute_of_day in from..24 * 60 {
+                if bit(self.hours, minute_of_day / 60) && bit(self.minutes, minute_of_day % 60) {
+                    return Some(PostAt::from_unix(day * DAY + minute_of_day * 60 - offset));
+                }
+            }
+        }
+        None
+    }
+
+    /// The expression as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    fn matches_day(&self, day: i64) -> bool {
+        let (month, day_of_month) = month_day(day);
+        if !bit(self.months, month) {
+            return false;
+        }
+        // Sunday is both 0 and 7.
+        let weekday = (day + 4).rem_euclid(7);
+        let day_ok = bit(self.days, day_of_month);
+        let weekday_ok = bit(self.weekdays, weekday) || (weekday == 0 && bit(self.weekdays, 7));
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday_ok,
+            (false, true) => day_ok,
+            (false, false) => day_ok || weekday_ok,
+        }
+    }
+}
+
+fn bit(bits: u64, n: i64) -> bool {
+    bits & (1 << n) != 0
+}
+
+/// Month (1-12) and day of month (1-31) for days since the epoch.
+fn month_day(day: i64) -> (i64, i64) {
+    // Howard Hinnant's civil_from_days, with years starting in March.
+    let z = day + 719_468;
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day_of_month = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    (month, day_of_month)
+}
+
+impl FromStr for Cron {
+    type Err = CronError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expanded = match s.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let parts: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = parts[..] else {
+            return Err(CronError::FieldCount(parts.len()));
+        };
+        Ok(Self {
+            source: s.trim().to_owned(),
+            minutes: FIELDS[0].parse(minute)?,
+            hours: FIELDS[1].parse(hour)?,
+            days: FIELDS[2].parse(day)?,
+            months: FIELDS[3].parse(month)?,
+            weekdays: FIELDS[4].parse(weekday)?,
+            any_day: day == "*",
+            any_weekday: weekday == "*",
+        })
+    }
+}
+
+impl fmt::Display for Cron {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Cron {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Cron {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Friday 2024-03-01 12:00:00 UTC.
+    const FRIDAY_NOON: i64 = 1_709_294_400;
+
+    fn next(expr: &str, after: i64, offset: i32) -> Option<i64> {
+        let cron: Cron = expr.parse().unwrap();
+        cron.next_after(PostAt::from_unix(after), offset)
+            .map(PostAt::unix)
+    }
+
+    #[test]
+    fn parses_fields_and_rejects_bad_ones() {
+        let cron: Cron = "*/15 9-17 1,15 jan-mar MON-FRI".parse().unwrap();
+        assert_eq!(cron.minutes, 1 | 1 << 15 | 1 << 30 | 1 << 45);
+        assert_eq!(cron.hours.count_ones(), 9);
+        assert_eq!(cron.months, 0b1110);
+        assert_eq!(cron.weekdays, 0b11_1110);
+        assert_eq!(cron.to_string(), "*/15 9-17 1,15 jan-mar MON-FRI");
+        assert_eq!("* * *".parse::<Cron>(), Err(CronError::FieldCount(3)));
+        for bad in [
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+        ] {
+            assert!(bad.parse::<Cron>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn finds_next_occurrence() {
+        // Later the same day, and never the current minute.
+        assert_eq!(
+            next("30 12 * * *", FRIDAY_NOON, 0),
+            Some(FRIDAY_NOON + 1800)
+        );
+        assert_eq!(next("0 12 * * *", FRIDAY_NOON, 0), Some(FRIDAY_NOON + DAY));
+        // Weekdays skip to Monday.
+        assert_eq!(
+            next("0 9 * * 1-5", FRIDAY_NOON, 0),
+            Some(FRIDAY_NOON + 3 * DAY - 3 * 3600)
+        );
+        // Sunday as 7; 09:00 at UTC-5 is 14:00 UTC.
+        assert_eq!(
+            next("0 9 * * 7", FRIDAY_NOON, -5 * 3600),
+            Some(FRIDAY_NOON + 2 * DAY + 2 * 3600)
+        );
+        // 2024 is a leap year.
+        assert_eq!(next("0 0 29 2 *", FRIDAY_NOON, 0), Some(1_835_395_200));
+        assert_eq!(next("0 0 30 2 *", FRIDAY_NOON, 0), None);
+        // Restricted day of month or weekday: the 15th or a Saturday.
+        assert_eq!(
+            next("0 0 15 * sat", FRIDAY_NOON, 0),
+            Some(FRIDAY_NOON + 12 * 3600)
+        );
+        assert_eq!(next("@monthly", FRIDAY_NOON, 0), Some(1_711_929_600));
+    }
+}
@@ -0,0 +1,238 @@
+//! Listing, cancelling and rescheduling scheduled messages.
+
+use thiserror::Error;
+
+use crate::api::chat::delete_scheduled_message::DeleteScheduledMessage;
+use crate::api::chat::payload::MessageContent;
+use crate::api::chat::schedule_message::ScheduleMessageResponse;
+use crate::api::chat::scheduled_messages_list::{ScheduledMessage, ScheduledMessagesList};
+use crate::client::Execute;
+use crate::id::{ChannelId, TeamId};
+
+use super::{PostAt, PostAtError};
+
+/// Selects scheduled messages by channel, text and posting time.
+///
+/// Unset criteria match everything.
+#[slaq_macros::builder]
+#[derive(Debug, Clone, Default)]
+pub struct ScheduledFilter {
+    /// Only messages posting to this channel ID.
+    pub channel: Option<ChannelId>,
+    /// Only messages whose text contains this string.
+    pub text: Option<String>,
+    /// Only messages posting at or after this time.
+    pub oldest: Option<PostAt>,
+    /// Only messages posting at or before this time.
+    pub latest: Option<PostAt>,
+}
+
+impl ScheduledFilter {
+    /// Returns `true` if `message` meets every criterion.
+    #[must_use]
+    pub fn matches(&self, message: &ScheduledMessage) -> bool {
+        self.channel
+            .as_ref()
+            .is_none_or(|channel| *channel == message.channel_id.as_str())
+            && self
+                .text
+                .as_ref()
+                .is_none_or(|text| message.text.contains(text.as_str()))
+            && self.oldest.is_none_or(|oldest| message.post_at >= oldest)
+            && self.latest.is_none_or(|latest| message.post_at <= latest)
+    }
+}
+
+/// An error from [`ScheduledMessages::reschedule`].
+#[derive(Debug, Error)]
+pub enum RescheduleError<E> {
+    #[error(transparent)]
+    PostAt(#[from] PostAtError),
+    #[error("Slack request failed: {0}")]
+    Client(E),
+}
+
+/// Manages an app's scheduled messages through a client.
+///
+/// Combines `chat.scheduledMessages.list`, `chat.deleteScheduledMessage`
+/// and `chat.scheduleMessage`:
+///
+/// ```no_run
+/// # fn run<C: slaq::client::Execute>(client: &C) -> Result<(), Box<dyn std::error::Error>>
+/// # where C::Error: std::error::Error + 'static {
+/// use slaq::schedule::{PostAt, ScheduledFilter, ScheduledMessages};
+///
+/// let scheduled = ScheduledMessages::new(client);
+/// let reminders = ScheduledFilter::new().channel("C123").text("reminder");
+/// for message in scheduled.list(&reminders)? {
+///     scheduled.reschedule(&message, PostAt::from(message.post_at.unix() + 3600))?;
+/// }
+/// scheduled.cancel(&ScheduledFilter::new().text("[draft]"))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ScheduledMessages<'a, C> {
+    client: &'a C,
+    team_id: Option<TeamId>,
+    page_size: Option<u32>,
+}
+
+impl<'a, C: Execute> ScheduledMessages<'a, C> {
+    /// Creates a manager using `client`.
+    #[must_use]
+    pub fn new(client: &'a C) -> Self {
+        Self {
+            client,
+            team_id: None,
+            page_size: None,
+        }
+    }
+
+    /// Sets the workspace to list, required with org-wide tokens.
+    #[must_use]
+    pub fn team_id(mut self, team_id: impl Into<TeamId>) -> Self {
+        self.team_id = Some(team_id.into());
+        self
+    }
+
+    /// Sets how many messages to request per page.
+    #[must_use]
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Lists all scheduled messages matching `filter`, following pagination
+    /// across all channels unless the filter names one.
+    ///
+    /// # Errors
+    /// Returns the transport error if a request fails.
+    pub fn list(&self, filter: &ScheduledFilter) -> Result<Vec<ScheduledMessage>, C::Error> {
+        let mut messages = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let request = ScheduledMessagesList {
+                channel: filter.channel.clone(),
+                cursor: cursor.take(),
+                oldest: filter.oldest.map(|t| t.to_string()),
+                latest: filter.latest.map(|t| t.to_string()),
+                limit: self.page_size,
+                team_id: self.team_id.clone(),
+            };
+            let resp = self.client.execute(request)?;
+            cursor = resp.next_cursor().map(str::to_owned);
+            messages.extend(
+                resp.scheduled_messages
+                    .into_iter()
+                    .filter(|message| filter.matches(message)),
+            );
+            if cursor.is_none() {
+                return Ok(messages);
+            }
+        }
+    }
+
+    /// Cancels every scheduled message matching `filter` and returns them.
+    ///
+    /// # Errors
+    /// Returns the transport error of the first failed request; messages
+    /// cancelled before it stay cancelled.
+    pub fn cancel(&self, filter: &ScheduledFilter) -> Result<Vec<ScheduledMessage>, C::Error> {
+        let messages = self.list(filter)?;
+        for message in &messages {
+            self.cancel_one(message)?;
+        }
+        Ok(messages)
+    }
+
+    /// Cancels one scheduled message.
+    ///
+    /// # Errors
+    /// Returns the transport error if the request fails.
+    pub fn cancel_one(&self, message: &ScheduledMessage) -> Result<(), C::Error> {
+        self.client
+            .execute(DeleteScheduledMessage::new(
+                message.channel_id.clone(),
+                message.id.clone(),
+            ))
+            .map(|_| ())
+    }
+
+    /// Moves a scheduled message to `post_at`, keeping the content Slack
+    /// lists for it: text, blocks and thread.
+    ///
+    /// Slack can't edit scheduled messages, so this schedules a copy and
+    /// cancels the original. Use [`ScheduledMessages::reschedule_with`] to
+    /// keep content Slack doesn't list, such as attachments.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if `post_at` is outside Slack's window,
+    /// before sending anything, or the transport error if a request fails.
+    pub fn reschedule(
+        &self,
+        message: &ScheduledMessage,
+        post_at: impl Into<PostAt>,
+    ) -> Result<ScheduleMessageResponse, RescheduleError<C::Error>> {
+        self.reschedule_with(message, post_at, message.content())
+    }
+
+    /// Replaces a scheduled message with `content` posting at `post_at`, in
+    /// the same channel and thread.
+    ///
+    /// The new message is scheduled before the original is cancelled, so a
+    /// failure can leave both scheduled but never loses the message.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if `post_at` is outside Slack's window,
+    /// before sending anything, or the transport error if a request fails.
+    pub fn reschedule_with(
+        &self,
+        message: &ScheduledMessage,
+        post_at: impl Into<PostAt>,
+        content: MessageContent,
+    ) -> Result<ScheduleMessageResponse, RescheduleError<C::Error>> {
+        let mut request = content.schedule_message(message.channel_id.clone(), post_at)?;
+        request.thread_ts.clone_from(&message.thread_ts);
+        let resp = self
+            .client
+            .execute(request)
+            .map_err(RescheduleError::Client)?;
+        self.cancel_one(message).map_err(RescheduleError::Client)?;
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(channel: &str, text: &str, post_at: i64) -> ScheduledMessage {
+        serde_json::from_value(serde_json::json!({
+            "id": "Q1",
+            "channel_id": channel,
+            "post_at": post_at,
+            "date_created": 0,
+            "text": text,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn filter_matches_all_criteria() {
+        let msg = message("C1", "daily reminder", 100);
+        assert!(ScheduledFilter::new().matches(&msg));
+        assert!(
+            ScheduledFilter::new()
+                .channel("C1")
+                .text("reminder")
+                .oldest(100)
+                .latest(100)
+                .matches(&msg)
+        );
+        assert!(!ScheduledFilter::new().channel("C2").matches(&msg));
+        assert!(!ScheduledFilter::new().text("weekly").matches(&msg));
+        assert!(!ScheduledFilter::new().oldest(101).matches(&msg));
+        assert!(!ScheduledFilter::new().latest(99).matches(&msg));
+    }
+}
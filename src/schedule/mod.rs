@@ -0,0 +1,39 @@
+//! Scheduling messages.
+//!
+//! `PostAt` is the time a scheduled message posts. `chat.scheduleMessage`
+//! takes one, and the scheduled messages Slack returns carry one. It
+//! converts from `SystemTime` and, with the `chrono` and `time` features,
+//! from those crates' date types. It checks Slack's window of up to 120 days
+//! ahead and builds times like "in 2 hours" or "next weekday at 09:00" in a
+//! recipient's time zone:
+//!
+//! ```
+//! use slaq::api::chat::schedule_message::ScheduleMessage;
+//! use slaq::schedule::PostAt;
+//!
+//! let tz_offset = -5 * 3600; // the recipient's `tz_offset`
+//! let standup = PostAt::now().next_weekday_at(9, 0, tz_offset)?;
+//! let msg = ScheduleMessage::new("C123", standup).text("Standup time!");
+//! msg.validate_post_at()?;
+//! # Ok::<(), slaq::schedule::PostAtError>(())
+//! ```
+//!
+//! `ScheduledMessages` manages messages already scheduled: it lists them
+//! across pages and channels, filters them with a `ScheduledFilter`, cancels
+//! matches in bulk and reschedules them.
+//!
+//! `Scheduler` goes beyond what Slack schedules itself: it keeps
+//! `RecurringJob`s on a `Cron` expression or far in the future in a local
+//! file, and schedules their occurrences as they come within Slack's window.
+
+mod cron;
+mod manager;
+mod post_at;
+mod recurring;
+
+pub use cron::{Cron, CronError};
+pub use manager::{RescheduleError, ScheduledFilter, ScheduledMessages};
+pub use post_at::{MAX_DAYS_AHEAD, PostAt, PostAtError};
+pub use recurring::{
+    Occurrence, Recurrence, RecurringJob, Scheduler, SchedulerError, StateError, SyncReport,
+};
@@ -0,0 +1,335 @@
+//! The time a scheduled message posts.
+
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
+
+use crate::id::Ts;
+
+/// How far ahead Slack accepts scheduled messages, in days.
+pub const MAX_DAYS_AHEAD: u64 = 120;
+
+const DAY: i64 = 86_400;
+
+/// When a scheduled message posts, in seconds since the Unix epoch.
+///
+/// Converts from `i64`, `SystemTime` and `Ts`, and with the `chrono` and
+/// `time` features from `chrono::DateTime` and `time::OffsetDateTime`.
+/// Deserializes from both the number and the string forms Slack returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+#[serde(transparent)]
+pub struct PostAt(i64);
+
+/// A `post_at` outside the window Slack accepts, or an invalid time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum PostAtError {
+    #[error("post_at {0} is not in the future")]
+    NotInFuture(i64),
+    #[error("post_at {0} is more than {MAX_DAYS_AHEAD} days ahead")]
+    TooFarAhead(i64),
+    #[error("{hour}:{minute:02} is not a valid time of day")]
+    InvalidTime { hour: u32, minute: u32 },
+}
+
+impl PostAt {
+    /// Creates a time from seconds since the Unix epoch.
+    #[must_use]
+    pub fn from_unix(secs: i64) -> Self {
+        Self(secs)
+    }
+
+    /// Seconds since the Unix epoch.
+    #[must_use]
+    pub fn unix(self) -> i64 {
+        self.0
+    }
+
+    /// The current time.
+    #[must_use]
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// The time `delay` from now.
+    #[must_use]
+    pub fn after(delay: Duration) -> Self {
+        (SystemTime::now() + delay).into()
+    }
+
+    /// The time `minutes` from now.
+    #[must_use]
+    pub fn in_minutes(minutes: u64) -> Self {
+        Self::after(Duration::from_secs(minutes * 60))
+    }
+
+    /// The time `hours` from now.
+    #[must_use]
+    pub fn in_hours(hours: u64) -> Self {
+        Self::after(Duration::from_secs(hours * 3600))
+    }
+
+    /// The time `days` from now.
+    #[must_use]
+    pub fn in_days(days: u64) -> Self {
+        Self::after(Duration::from_secs(days * 86_400))
+    }
+
+    /// The next `hour:minute` strictly after this time, in the time zone
+    /// `utc_offset` seconds from UTC, such as a user's `tz_offset`.
+    ///
+    /// # Errors
+    /// Returns `PostAtError::InvalidTime` if `hour` is over 23 or `minute`
+    /// over 59.
+    pub fn next_at(self, hour: u32, minute: u32, utc_offset: i32) -> Result<Self, PostAtError> {
+        self.next_matching(hour, minute, utc_offset, |_| true)
+    }
+
+    /// Like [`PostAt::next_at`], skipping Saturdays and Sundays.
+    ///
+    /// ```
+    /// use slaq::schedule::PostAt;
+    ///
+    /// // Friday 2024-03-01 18:00 UTC; the recipient is at UTC-5.
+    /// let friday = PostAt::from_unix(1_709_316_000);
+    /// let monday_9am = friday.next_weekday_at(9, 0, -5 * 3600).unwrap();
+    /// assert_eq!(monday_9am.unix(), 1_709_560_800); // 2024-03-04 14:00 UTC
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `PostAtError::InvalidTime` if `hour` is over 23 or `minute`
+    /// over 59.
+    pub fn next_weekday_at(
+        self,
+        hour: u32,
+        minute: u32,
+        utc_offset: i32,
+    ) -> Result<Self, PostAtError> {
+        self.next_matching(hour, minute, utc_offset, |day| weekday(day) < 5)
+    }
+
+    fn next_matching(
+        self,
+        hour: u32,
+        minute: u32,
+        utc_offset: i32,
+        accept: impl Fn(i64) -> bool,
+    ) -> Result<Self, PostAtError> {
+        if hour > 23 || minute > 59 {
+            return Err(PostAtError::InvalidTime { hour, minute });
+        }
+        let offset = i64::from(utc_offset);
+        let local = self.0 + offset;
+        let time_of_day = i64::from(hour) * 3600 + i64::from(minute) * 60;
+        let mut day = local.div_euclid(DAY);
+        if day * DAY + time_of_day <= local {
+            day += 1;
+        }
+        while !accept(day) {
+            day += 1;
+        }
+        Ok(Self(day * DAY + time_of_day - offset))
+    }
+
+    /// Checks that the time is in the future and at most
+    /// [`MAX_DAYS_AHEAD`] days ahead, as `chat.scheduleMessage` requires.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if the time is outside that window.
+    pub fn validate(self) -> Result<(), PostAtError> {
+        self.validate_at(Self::now())
+    }
+
+    /// Like [`PostAt::validate`], relative to `now`.
+    ///
+    /// # Errors
+    /// Returns a `PostAtError` if the time is outside the window.
+    pub fn validate_at(self, now: PostAt) -> Result<(), PostAtError> {
+        if self.0 <= now.0 {
+            Err(PostAtError::NotInFuture(self.0))
+        } else if self.0 - now.0 > MAX_DAYS_AHEAD.cast_signed() * DAY {
+            Err(PostAtError::TooFarAhead(self.0))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Day of the week for days since the epoch, Monday = 0.
+fn weekday(day: i64) -> i64 {
+    // 1970-01-01 was a Thursday.
+    (day + 3).rem_euclid(7)
+}
+
+impl fmt::Display for PostAt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<i64> for PostAt {
+    fn from(secs: i64) -> Self {
+        Self(secs)
+    }
+}
+
+impl From<PostAt> for i64 {
+    fn from(post_at: PostAt) -> Self {
+        post_at.0
+    }
+}
+
+impl PartialEq<i64> for PostAt {
+    fn eq(&self, other: &i64) -> bool {
+        self.0 == *other
+    }
+}
+
+/// Times before the epoch map to negative seconds; sub-second precision is
+/// truncated.
+impl From<SystemTime> for PostAt {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(since) => Self(since.as_secs().cast_signed()),
+            Err(before) => Self(-before.duration().as_secs().cast_signed()),
+        }
+    }
+}
+
+impl From<PostAt> for SystemTime {
+    fn from(post_at: PostAt) -> Self {
+        let secs = Duration::from_secs(post_at.0.unsigned_abs());
+        if post_at.0 >= 0 {
+            UNIX_EPOCH + secs
+        } else {
+            UNIX_EPOCH - secs
+        }
+    }
+}
+
+impl From<Ts> for PostAt {
+    fn from(ts: Ts) -> Self {
+        Self(ts.secs().cast_signed())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> From<chrono::DateTime<Tz>> for PostAt {
+    fn from(time: chrono::DateTime<Tz>) -> Self {
+        Self(time.timestamp())
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for PostAt {
+    fn from(time: time::OffsetDateTime) -> Self {
+        Self(time.unix_timestamp())
+    }
+}
+
+impl<'de> Deserialize<'de> for PostAt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Number(i64),
+            String(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Number(secs) => Ok(Self(secs)),
+            Raw::String(s) => s.parse().map(Self).map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Friday 2024-03-01 12:00:00 UTC.
+    const FRIDAY_NOON: i64 = 1_709_294_400;
+
+    #[test]
+    fn window_is_future_and_at_most_120_days() {
+        let now = PostAt::from_unix(FRIDAY_NOON);
+        assert_eq!(
+            now.validate_at(now),
+            Err(PostAtError::NotInFuture(FRIDAY_NOON))
+        );
+        assert!(
+            PostAt::from_unix(FRIDAY_NOON + 120 * DAY)
+                .validate_at(now)
+                .is_ok()
+        );
+        assert_eq!(
+            PostAt::from_unix(FRIDAY_NOON + 120 * DAY + 1).validate_at(now),
+            Err(PostAtError::TooFarAhead(FRIDAY_NOON + 120 * DAY + 1))
+        );
+        assert!(PostAt::in_hours(2).validate().is_ok());
+    }
+
+    #[test]
+    fn next_times_respect_offset_and_weekends() {
+        let noon = PostAt::from_unix(FRIDAY_NOON);
+        // 09:00 UTC has passed, so the next one is Saturday's.
+        assert_eq!(
+            noon.next_at(9, 0, 0),
+            Ok(PostAt(FRIDAY_NOON + DAY - 3 * 3600))
+        );
+        // 09:00 in UTC+9 is 00:00 UTC, also already passed.
+        assert_eq!(
+            noon.next_at(9, 0, 9 * 3600),
+            Ok(PostAt(FRIDAY_NOON + 12 * 3600))
+        );
+        // Later today still counts.
+        assert_eq!(noon.next_at(13, 30, 0), Ok(PostAt(FRIDAY_NOON + 5400)));
+        // Skips the weekend to Monday.
+        assert_eq!(
+            noon.next_weekday_at(9, 0, 0),
+            Ok(PostAt(FRIDAY_NOON + 3 * DAY - 3 * 3600))
+        );
+        assert_eq!(
+            noon.next_weekday_at(13, 0, 0),
+            Ok(PostAt(FRIDAY_NOON + 3600))
+        );
+    }
+
+    #[test]
+    fn next_times_reject_invalid_times_of_day() {
+        let noon = PostAt::from_unix(FRIDAY_NOON);
+        assert_eq!(
+            noon.next_at(24, 0, 0),
+            Err(PostAtError::InvalidTime {
+                hour: 24,
+                minute: 0
+            })
+        );
+        assert_eq!(
+            noon.next_weekday_at(9, 60, 0),
+            Err(PostAtError::InvalidTime {
+                hour: 9,
+                minute: 60
+            })
+        );
+        assert_eq!(
+            PostAtError::InvalidTime {
+                hour: 9,
+                minute: 60
+            }
+            .to_string(),
+            "9:60 is not a valid time of day"
+        );
+    }
+
+    #[test]
+    fn converts_and_deserializes_both_forms() {
+        let post_at = PostAt::from_unix(FRIDAY_NOON);
+        assert_eq!(PostAt::from(SystemTime::from(post_at)), post_at);
+        let number: PostAt = serde_json::from_str("1709294400").unwrap();
+        let string: PostAt = serde_json::from_str(r#""1709294400""#).unwrap();
+        assert_eq!(number, post_at);
+        assert_eq!(string, post_at);
+        assert_eq!(serde_json::to_string(&post_at).unwrap(), "1709294400");
+    }
+}
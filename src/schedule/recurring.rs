@@ -0,0 +1,526 @@
+//! A local scheduler for recurring and far-future messages.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+use thiserror::Error;
+
+use crate::api::attachment::Attachment;
+use crate::api::chat::payload::{ContentError, MessageContent, MessagePayload};
+use crate::api::chat::schedule_message::ScheduleMessage;
+use crate::api::chat::scheduled_messages_list::ScheduledMessage;
+use crate::blocks::Block;
+use crate::client::Execute;
+use crate::id::{ChannelId, Ts};
+
+use super::{Cron, MAX_DAYS_AHEAD, PostAt, PostAtError, ScheduledFilter, ScheduledMessages};
+
+const DAY: i64 = 86_400;
+
+/// When a [`RecurringJob`] posts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Once, at any time in the future, including beyond Slack's window.
+    Once { at: PostAt },
+    /// At every match of a cron expression, evaluated `utc_offset` seconds
+    /// from UTC.
+    Cron { cron: Cron, utc_offset: i32 },
+}
+
+impl Recurrence {
+    /// Posts once at `at`.
+    #[must_use]
+    pub fn once(at: impl Into<PostAt>) -> Self {
+        Self::Once { at: at.into() }
+    }
+
+    /// Posts at every match of `cron` in the time zone `utc_offset` seconds
+    /// from UTC.
+    #[must_use]
+    pub fn cron(cron: Cron, utc_offset: i32) -> Self {
+        Self::Cron { cron, utc_offset }
+    }
+
+    /// The first occurrence after `after`.
+    #[must_use]
+    pub fn next_after(&self, after: PostAt) -> Option<PostAt> {
+        match self {
+            Self::Once { at } => (*at > after).then_some(*at),
+            Self::Cron { cron, utc_offset } => cron.next_after(after, *utc_offset),
+        }
+    }
+}
+
+/// A message the [`Scheduler`] posts on a [`Recurrence`].
+#[slaq_macros::builder]
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecurringJob {
+    /// Identifies the job in the scheduler's state.
+    pub id: String,
+    /// Channel to post to.
+    pub channel: ChannelId,
+    /// When to post.
+    pub recurrence: Recurrence,
+    /// Message text, or the notification fallback when `blocks` are set.
+    pub text: Option<String>,
+    /// Structured blocks.
+    pub blocks: Option<Vec<Block>>,
+    /// Message text formatted in markdown. Not to be combined with `blocks` or `text`.
+    pub markdown_text: Option<String>,
+    /// Legacy secondary attachments.
+    pub attachments: Option<Vec<Attachment>>,
+    /// Thread to post each occurrence into.
+    pub thread_ts: Option<Ts>,
+    /// Stop posting after this time.
+    pub until: Option<PostAt>,
+}
+
+impl RecurringJob {
+    /// Replaces the job's text, blocks, markdown text and attachments with
+    /// `content`.
+    #[must_use]
+    pub fn with_content(mut self, content: MessageContent) -> Self {
+        self.text = content.text;
+        self.blocks = content.blocks;
+        self.markdown_text = content.markdown_text;
+        self.attachments = content.attachments;
+        self
+    }
+
+    /// The occurrences after `after` up to and including `until`, at most
+    /// `limit` of them.
+    #[must_use]
+    pub fn occurrences(&self, after: PostAt, until: PostAt, limit: usize) -> Vec<PostAt> {
+        let until = self.until.map_or(until, |end| end.min(until));
+        let mut times = Vec::new();
+        let mut last = after;
+        while times.len() < limit {
+            match self.recurrence.next_after(last) {
+                Some(next) if next <= until => {
+                    times.push(next);
+                    last = next;
+                }
+                _ => break,
+            }
+        }
+        times
+    }
+
+    fn schedule_message(&self, post_at: PostAt) -> ScheduleMessage {
+        let content = MessageContent {
+            text: self.text.clone(),
+            blocks: self.blocks.clone(),
+            markdown_text: self.markdown_text.clone(),
+            attachments: self.attachments.clone(),
+            ..MessageContent::default()
+        };
+        // `Scheduler::sync` checks `post_at` against its own clock.
+        let mut request =
+            ScheduleMessage::new(self.channel.clone(), post_at).replace_content(content);
+        request.thread_ts.clone_from(&self.thread_ts);
+        request
+    }
+
+    /// A stable hash of what each occurrence posts, to tell when a replaced
+    /// job's scheduled occurrences are out of date.
+    fn content_hash(&self) -> u64 {
+        let content = (
+            &self.text,
+            &self.blocks,
+            &self.markdown_text,
+            &self.attachments,
+            &self.thread_ts,
+        );
+        let bytes = serde_json::to_vec(&content).expect("job content serializes to JSON");
+        // 64-bit FNV-1a, which unlike `DefaultHasher` is the same across
+        // Rust releases, so hashes in the state file stay valid.
+        bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+        })
+    }
+
+    /// Whether `message` could be this job's occurrence at `post_at`.
+    fn is_occurrence(&self, message: &ScheduledMessage, post_at: PostAt) -> bool {
+        self.channel == message.channel_id.as_str()
+            && message.post_at == post_at
+            && self.text.as_ref().is_none_or(|text| message.text == *text)
+    }
+}
+
+/// One occurrence of a job scheduled with Slack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Occurrence {
+    /// The job's ID.
+    pub job: String,
+    /// The channel it posts to.
+    pub channel: ChannelId,
+    /// When it posts.
+    pub post_at: PostAt,
+    /// Hash of the job's content when it was scheduled. Records without one
+    /// are treated as out of date and rescheduled.
+    #[serde(default)]
+    pub content_hash: u64,
+    /// Slack's ID for the scheduled message.
+    pub scheduled_message_id: String,
+}
+
+/// What [`Scheduler::sync`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Occurrences newly scheduled with Slack.
+    pub scheduled: Vec<Occurrence>,
+    /// Occurrences found scheduled in Slack but missing from the state,
+    /// such as after a crash before saving.
+    pub adopted: Vec<Occurrence>,
+    /// Occurrences cancelled because their job was removed or changed.
+    pub cancelled: Vec<Occurrence>,
+}
+
+/// An error reading or writing the [`Scheduler`]'s state file.
+#[derive(Debug, Error)]
+pub enum StateError {
+    #[error("failed to access scheduler state: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid scheduler state: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// An error from [`Scheduler::sync`].
+#[derive(Debug, Error)]
+pub enum SchedulerError<E> {
+    #[error(transparent)]
+    State(#[from] StateError),
+    #[error(transparent)]
+    PostAt(#[from] PostAtError),
+    #[error(transparent)]
+    Content(#[from] ContentError),
+    #[error("Slack request failed: {0}")]
+    Client(E),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    jobs: Vec<RecurringJob>,
+    scheduled: Vec<Occurrence>,
+}
+
+/// Posts recurring and far-future messages through `chat.scheduleMessage`.
+///
+/// Slack's scheduled messages can't repeat and must post within 120 days.
+/// The scheduler keeps jobs in a JSON file and, on each
+/// [`Scheduler::sync`], schedules their upcoming occurrences that fall
+/// inside Slack's window. Run `sync` periodically, e.g. daily, to keep the
+/// window filled.
+///
+/// Every occurrence scheduled is recorded in the file. `sync` compares the
+/// record with `chat.scheduledMessages.list` first, so after a restart or a
+/// crash it adopts occurrences already in Slack instead of scheduling them
+/// twice, and cancels those whose job was removed or changed.
+///
+/// ```no_run
+/// # fn run<C: slaq::client::Execute>(client: &C) -> Result<(), Box<dyn std::error::Error>>
+/// # where C::Error: std::error::Error + 'static {
+/// use slaq::schedule::{PostAt, Recurrence, RecurringJob, Scheduler};
+///
+/// let mut scheduler = Scheduler::open("schedule.json")?;
+/// scheduler.add(
+///     RecurringJob::new("standup", "C123", Recurrence::cron("0 9 * * mon-fri".parse()?, 3600))
+///         .text("Standup in the huddle!"),
+/// )?;
+/// scheduler.sync(client, PostAt::now())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Scheduler {
+    path: PathBuf,
+    state: State,
+    per_job: usize,
+    horizon_days: u64,
+}
+
+impl Scheduler {
+    /// Opens the scheduler state at `path`, starting empty if the file
+    /// doesn't exist.
+    ///
+    /// # Errors
+    /// Returns a `StateError` if the file can't be read or parsed.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, StateError> {
+        let path = path.into();
+        let state = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => State::default(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self {
+            path,
+            state,
+            per_job: 10,
+            horizon_days: MAX_DAYS_AHEAD,
+        })
+    }
+
+    /// Sets how many upcoming occurrences of each job to keep scheduled,
+    /// 10 by default.
+    #[must_use]
+    pub fn per_job(mut self, per_job: usize) -> Self {
+        self.per_job = per_job;
+        self
+    }
+
+    /// Sets how far ahead to schedule, at most and by default 120 days.
+    #[must_use]
+    pub fn horizon_days(mut self, days: u64) -> Self {
+        self.horizon_days = days.min(MAX_DAYS_AHEAD);
+        self
+    }
+
+    /// The path of the state file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The jobs.
+    #[must_use]
+    pub fn jobs(&self) -> &[RecurringJob] {
+        &self.state.jobs
+    }
+
+    /// The occurrences scheduled with Slack as of the last save.
+    #[must_use]
+    pub fn scheduled(&self) -> &[Occurrence] {
+        &self.state.scheduled
+    }
+
+    /// Adds a job, replacing any with the same ID, and saves.
+    ///
+    /// Occurrences of a replaced job that no longer match, in time or in
+    /// content, are cancelled and rescheduled on the next [`Scheduler::sync`].
+    ///
+    /// # Errors
+    /// Returns a `StateError` if the state can't be saved.
+    pub fn add(&mut self, job: RecurringJob) -> Result<(), StateError> {
+        self.state.jobs.retain(|j| j.id != job.id);
+        self.state.jobs.push(job);
+        self.save()
+    }
+
+    /// Removes a job and saves, returning it if it existed.
+    ///
+    /// Its scheduled occurrences are cancelled on the next
+    /// [`Scheduler::sync`].
+    ///
+    /// # Errors
+    /// Returns a `StateError` if the state can't be saved.
+    pub fn remove(&mut self, id: &str) -> Result<Option<RecurringJob>, StateError> {
+        let Some(index) = self.state.jobs.iter().position(|j| j.id == id) else {
+            return Ok(None);
+        };
+        let job = self.state.jobs.remove(index);
+        self.save()?;
+        Ok(Some(job))
+    }
+
+    /// Reconciles the state with Slack as of `now` and schedules upcoming
+    /// occurrences, saving after every change.
+    ///
+    /// 1. Forgets occurrences Slack no longer lists, because they posted or
+    ///    were cancelled elsewhere.
+    /// 2. Cancels occurrences whose job was removed, no longer produces
+    ///    them, or now posts different content.
+    /// 3. Adopts unrecorded scheduled messages matching a wanted occurrence
+    ///    by channel, time and text.
+    /// 4. Schedules the remaining wanted occurrences.
+    ///
+    /// # Errors
+    /// Returns an error if a request fails, the state can't be saved, a job's
+    /// content is invalid or an occurrence falls outside Slack's window as of
+    /// `now`. Changes made before the error are saved, so `sync` can be retried.
+    pub fn sync<C: Execute>(
+        &mut self,
+        client: &C,
+        now: PostAt,
+    ) -> Result<SyncReport, SchedulerError<C::Error>> {
+        let manager = ScheduledMessages::new(client);
+        let mut listed = manager
+            .list(&ScheduledFilter::new())
+            .map_err(SchedulerError::Client)?;
+        let mut report = SyncReport::default();
+
+        let listed_ids: HashSet<&str> = listed.iter().map(|m| m.id.as_str()).collect();
+        self.state
+            .scheduled
+            .retain(|o| listed_ids.contains(o.scheduled_message_id.as_str()));
+
+        let horizon = PostAt::from_unix(now.unix() + self.horizon_days.cast_signed() * DAY);
+        let wanted: Vec<(usize, PostAt)> = self
+            .state
+            .jobs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, job)| {
+                job.occurrences(now, horizon, self.per_job)
+                    .into_iter()
+                    .map(move |t| (i, t))
+            })
+            .collect();
+        let hashes: Vec<u64> = self
+            .state
+            .jobs
+            .iter()
+            .map(RecurringJob::content_hash)
+            .collect();
+        let matches = |o: &Occurrence, jobs: &[RecurringJob], i: usize, post_at: PostAt| {
+            jobs[i].id == o.job
+                && jobs[i].channel == o.channel
+                && o.post_at == post_at
+                && o.content_hash == hashes[i]
+        };
+        let is_wanted = |o: &Occurrence, jobs: &[RecurringJob]| {
+            wanted.iter().any(|&(i, t)| matches(o, jobs, i, t))
+        };
+
+        let mut index = 0;
+        while index < self.state.scheduled.len() {
+            let occurrence = &self.state.scheduled[index];
+            if occurrence.post_at <= now || is_wanted(occurrence, &self.state.jobs) {
+                index += 1;
+                continue;
+            }
+            if let Some(pos) = listed
+                .iter()
+                .position(|m| m.id == occurrence.scheduled_message_id)
+            {
+                manager
+                    .cancel_one(&listed[pos])
+                    .map_err(SchedulerError::Client)?;
+                // Never adopt it below.
+                listed.swap_remove(pos);
+            }
+            report.cancelled.push(self.state.scheduled.remove(index));
+            self.save()?;
+        }
+
+        let recorded: HashSet<String> = self
+            .state
+            .scheduled
+            .iter()
+            .map(|o| o.scheduled_message_id.clone())
+            .collect();
+        listed.retain(|m| !recorded.contains(&m.id));
+
+        for (i, post_at) in wanted {
+            let job = &self.state.jobs[i];
+            let scheduled = &self.state.scheduled;
+            if scheduled
+                .iter()
+                .any(|o| matches(o, &self.state.jobs, i, post_at))
+            {
+                continue;
+            }
+            let occurrence = |scheduled_message_id| Occurrence {
+                job: job.id.clone(),
+                channel: job.channel.clone(),
+                post_at,
+                content_hash: hashes[i],
+                scheduled_message_id,
+            };
+            let occurrence =
+                if let Some(pos) = listed.iter().position(|m| job.is_occurrence(m, post_at)) {
+                    let occurrence = occurrence(listed.swap_remove(pos).id);
+                    report.adopted.push(occurrence.clone());
+                    occurrence
+                } else {
+                    post_at.validate_at(now)?;
+                    let request = job.schedule_message(post_at);
+                    request.validate()?;
+                    let resp = client.execute(request).map_err(SchedulerError::Client)?;
+                    let occurrence = occurrence(resp.scheduled_message_id);
+                    report.scheduled.push(occurrence.clone());
+                    occurrence
+                };
+            self.state.scheduled.push(occurrence);
+            self.save()?;
+        }
+        self.state
+            .jobs
+            .retain(|job| !matches!(job.recurrence, Recurrence::Once { at } if at <= now));
+        self.save()?;
+        Ok(report)
+    }
+
+    /// Writes the state to a temporary file and renames it over the state
+    /// file, so a crash never leaves it half-written.
+    fn save(&self) -> Result<(), StateError> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&self.state)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Friday 2024-03-01 12:00:00 UTC.
+    const FRIDAY_NOON: i64 = 1_709_294_400;
+
+    #[test]
+    fn occurrences_stop_at_limit_horizon_and_until() {
+        let now = PostAt::from_unix(FRIDAY_NOON);
+        let daily = RecurringJob::new(
+            "daily",
+            "C1",
+            Recurrence::cron("0 9 * * *".parse().unwrap(), 0),
+        );
+        let far = PostAt::from_unix(FRIDAY_NOON + 365 * DAY);
+        assert_eq!(daily.occurrences(now, far, 3).len(), 3);
+        assert_eq!(
+            daily
+                .occurrences(now, PostAt::from_unix(FRIDAY_NOON + 2 * DAY), 10)
+                .len(),
+            2
+        );
+        let ending = daily.until(FRIDAY_NOON + DAY);
+        assert_eq!(
+            ending.occurrences(now, far, 10),
+            [PostAt::from_unix(FRIDAY_NOON + DAY - 3 * 3600)]
+        );
+
+        let later = RecurringJob::new("later", "C1", Recurrence::once(FRIDAY_NOON + 200 * DAY));
+        let window = PostAt::from_unix(FRIDAY_NOON + 120 * DAY);
+        assert!(later.occurrences(now, window, 10).is_empty());
+        assert_eq!(later.occurrences(now, far, 10).len(), 1);
+    }
+
+    #[test]
+    fn jobs_round_trip_json() {
+        let job = RecurringJob::new(
+            "standup",
+            "C1",
+            Recurrence::cron("30 9 * * 1-5".parse().unwrap(), -18_000),
+        )
+        .text("Standup!");
+        let json = serde_json::to_value(&job).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "id": "standup",
+                "channel": "C1",
+                "recurrence": {"type": "cron", "cron": "30 9 * * 1-5", "utc_offset": -18_000},
+                "text": "Standup!",
+            })
+        );
+        let back: RecurringJob = serde_json::from_value(json).unwrap();
+        assert_eq!(back.recurrence, job.recurrence);
+    }
+}
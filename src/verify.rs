@@ -0,0 +1,297 @@
+//! Verifying that inbound requests come from Slack.
+//!
+//! Slack signs Events API, interactivity and slash command requests with
+//! the app's signing secret. `Verifier` checks the `X-Slack-Signature` and
+//! `X-Slack-Request-Timestamp` headers against the raw request body, using
+//! the `v0` HMAC-SHA256 scheme:
+//!
+//! ```
+//! use slaq::verify::{Verifier, sign};
+//!
+//! let body = b"token=xyz&command=%2Fdeploy";
+//! let timestamp = std::time::SystemTime::now()
+//!     .duration_since(std::time::UNIX_EPOCH)
+//!     .unwrap()
+//!     .as_secs()
+//!     .to_string();
+//! let signature = sign("8f742231b10e8888abcd99yyyzzz85a5", &timestamp, body);
+//!
+//! // During a rotation, accept both the new and the old secret.
+//! let verifier = Verifier::new("8f742231b10e8888abcd99yyyzzz85a5").secret("old-secret");
+//! assert!(verifier.verify(&timestamp, &signature, body).is_ok());
+//! ```
+//!
+//! Verify the body exactly as received, before parsing it. The module has no
+//! HTTP dependencies and works without the `transport-reqwest` feature.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+/// The header carrying the request signature.
+pub const SIGNATURE_HEADER: &str = "X-Slack-Signature";
+
+/// The header carrying the time the request was signed.
+pub const TIMESTAMP_HEADER: &str = "X-Slack-Request-Timestamp";
+
+/// How old a request may be by default, as Slack recommends.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_mins(5);
+
+const VERSION: &str = "v0";
+
+/// Why a request failed verification.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum VerifyError {
+    #[error("missing {0} header")]
+    MissingHeader(&'static str),
+    #[error("invalid request timestamp: {0:?}")]
+    InvalidTimestamp(String),
+    #[error("request timestamp {0} is outside the allowed window")]
+    Expired(i64),
+    #[error("malformed request signature")]
+    MalformedSignature,
+    #[error("request signature doesn't match any signing secret")]
+    SignatureMismatch,
+}
+
+/// Checks request signatures against one or more signing secrets.
+#[derive(Clone)]
+pub struct Verifier {
+    secrets: Vec<String>,
+    max_age: Duration,
+}
+
+impl Verifier {
+    /// Creates a verifier for the app's signing secret.
+    #[must_use]
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self {
+            secrets: vec![secret.into()],
+            max_age: DEFAULT_MAX_AGE,
+        }
+    }
+
+    /// Also accepts `secret`, e.g. the previous secret while rotating.
+    #[must_use]
+    pub fn secret(mut self, secret: impl Into<String>) -> Self {
+        self.secrets.push(secret.into());
+        self
+    }
+
+    /// Sets how far a request's timestamp may be from now, in either
+    /// direction, before it is rejected as a replay. Defaults to
+    /// [`DEFAULT_MAX_AGE`].
+    #[must_use]
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Verifies a request from its timestamp and signature header values
+    /// and raw body.
+    ///
+    /// # Errors
+    /// Returns a `VerifyError` if the timestamp is invalid or outside the
+    /// replay window, or the signature doesn't match any secret.
+    pub fn verify(&self, timestamp: &str, signature: &str, body: &[u8]) -> Result<(), VerifyError> {
+        self.verify_at(timestamp, signature, body, SystemTime::now())
+    }
+
+    /// Like [`Verifier::verify`], with the replay window relative to `now`.
+    ///
+    /// # Errors
+    /// Returns a `VerifyError` if verification fails.
+    pub fn verify_at(
+        &self,
+        timestamp: &str,
+        signature: &str,
+        body: &[u8],
+        now: SystemTime,
+    ) -> Result<(), VerifyError> {
+        let secs: i64 = timestamp
+            .trim()
+            .parse()
+            .map_err(|_| VerifyError::InvalidTimestamp(timestamp.to_owned()))?;
+        let now = match now.duration_since(UNIX_EPOCH) {
+            Ok(since) => since.as_secs().cast_signed(),
+            Err(before) => -before.duration().as_secs().cast_signed(),
+        };
+        if secs.abs_diff(now) > self.max_age.as_secs() {
+            return Err(VerifyError::Expired(secs));
+        }
+
+        let expected = signature
+            .strip_prefix(VERSION)
+            .and_then(|s| s.strip_prefix('='))
+            .and_then(decode_hex)
+            .ok_or(VerifyError::MalformedSignature)?;
+        let matched = self.secrets.iter().any(|secret| {
+            // `verify_slice` compares in constant time.
+            mac(secret, timestamp.trim(), body)
+                .verify_slice(&expected)
+                .is_ok()
+        });
+        if matched {
+            Ok(())
+        } else {
+            Err(VerifyError::SignatureMismatch)
+        }
+    }
+
+    /// Verifies a request, looking up its headers with `header`, which is
+    /// given the header names in their canonical case.
+    ///
+    /// ```
+    /// # use slaq::verify::{Verifier, VerifyError};
+    /// # use std::collections::HashMap;
+    /// let headers: HashMap<String, String> = HashMap::new();
+    /// let verifier = Verifier::new("secret");
+    /// let result = verifier.verify_headers(|name| headers.get(name).map(String::as_str), b"");
+    /// assert_eq!(result, Err(VerifyError::MissingHeader("X-Slack-Request-Timestamp")));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `VerifyError::MissingHeader` if a header is absent, or any
+    /// other `VerifyError` if verification fails.
+    pub fn verify_headers<'a>(
+        &self,
+        header: impl Fn(&str) -> Option<&'a str>,
+        body: &[u8],
+    ) -> Result<(), VerifyError> {
+        let timestamp =
+            header(TIMESTAMP_HEADER).ok_or(VerifyError::MissingHeader(TIMESTAMP_HEADER))?;
+        let signature =
+            header(SIGNATURE_HEADER).ok_or(VerifyError::MissingHeader(SIGNATURE_HEADER))?;
+        self.verify(timestamp, signature, body)
+    }
+}
+
+/// Secrets are redacted.
+impl std::fmt::Debug for Verifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Verifier")
+            .field("secrets", &self.secrets.len())
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
+
+/// Computes the `X-Slack-Signature` value for a request, e.g. to test
+/// handlers.
+#[must_use]
+pub fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let digest = mac(secret, timestamp, body).finalize().into_bytes();
+    let mut signature = String::with_capacity(VERSION.len() + 1 + digest.len() * 2);
+    signature.push_str(VERSION);
+    signature.push('=');
+    for byte in digest {
+        signature.push(char::from(HEX[usize::from(byte >> 4)]));
+        signature.push(char::from(HEX[usize::from(byte & 0xf)]));
+    }
+    signature
+}
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+
+fn mac(secret: &str, timestamp: &str, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(VERSION.as_bytes());
+    mac.update(b":");
+    mac.update(timestamp.as_bytes());
+    mac.update(b":");
+    mac.update(body);
+    mac
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    let digit = |b: u8| {
+        char::from(b)
+            .to_digit(16)
+            .and_then(|d| u8::try_from(d).ok())
+    };
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| Some(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example from Slack's documentation.
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+    const TIMESTAMP: &str = "1531420618";
+    const BODY: &[u8] = b"token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+    const SIGNATURE: &str = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+
+    fn at(secs: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn matches_slack_example() {
+        assert_eq!(sign(SECRET, TIMESTAMP, BODY), SIGNATURE);
+        let verifier = Verifier::new(SECRET);
+        assert_eq!(
+            verifier.verify_at(TIMESTAMP, SIGNATURE, BODY, at(1_531_420_618)),
+            Ok(())
+        );
+        assert_eq!(
+            verifier.verify_at(TIMESTAMP, SIGNATURE, b"tampered", at(1_531_420_618)),
+            Err(VerifyError::SignatureMismatch)
+        );
+        assert_eq!(
+            verifier.verify_at(TIMESTAMP, "v1=abcd", BODY, at(1_531_420_618)),
+            Err(VerifyError::MalformedSignature)
+        );
+    }
+
+    #[test]
+    fn enforces_replay_window() {
+        let verifier = Verifier::new(SECRET);
+        assert!(
+            verifier
+                .verify_at(TIMESTAMP, SIGNATURE, BODY, at(1_531_420_618 + 300))
+                .is_ok()
+        );
+        assert_eq!(
+            verifier.verify_at(TIMESTAMP, SIGNATURE, BODY, at(1_531_420_618 + 301)),
+            Err(VerifyError::Expired(1_531_420_618))
+        );
+        assert_eq!(
+            verifier.verify_at(TIMESTAMP, SIGNATURE, BODY, at(1_531_420_618 - 301)),
+            Err(VerifyError::Expired(1_531_420_618))
+        );
+        let lenient = verifier.max_age(Duration::from_hours(1));
+        assert!(
+            lenient
+                .verify_at(TIMESTAMP, SIGNATURE, BODY, at(1_531_420_618 + 3000))
+                .is_ok()
+        );
+        assert_eq!(
+            lenient.verify_at("soon", SIGNATURE, BODY, at(1_531_420_618)),
+            Err(VerifyError::InvalidTimestamp("soon".into()))
+        );
+    }
+
+    #[test]
+    fn accepts_any_configured_secret() {
+        let now = at(1_531_420_618);
+        let rotated = Verifier::new("new-secret").secret(SECRET);
+        assert!(rotated.verify_at(TIMESTAMP, SIGNATURE, BODY, now).is_ok());
+        let other = Verifier::new("new-secret");
+        assert_eq!(
+            other.verify_at(TIMESTAMP, SIGNATURE, BODY, now),
+            Err(VerifyError::SignatureMismatch)
+        );
+        assert_eq!(decode_hex("0aFf"), Some(vec![0x0a, 0xff]));
+        assert_eq!(decode_hex("0g"), None);
+    }
+}
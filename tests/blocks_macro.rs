@@ -0,0 +1,35 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::chat::post_message::PostMessage;
+use slaq::blocks::{Button, Element, Text};
+use slaq::mrkdwn;
+
+#[test]
+fn blocks_macro_feeds_post_message() {
+    let services = [("api", true), ("worker", false)];
+    let json = PostMessage::new("C1")
+        .blocks(slaq::blocks![
+            header("Deploy"),
+            for (name, ok) in (services) {
+                section(mrkdwn::bold(name) + if ok { " up" } else { " down" })
+                    .accessory(Button::new("Logs").action_id(format!("logs-{name}")).build()),
+            }
+            if (services.iter().any(|(_, ok)| !ok)) {
+                actions(Button::new("Roll back").action_id("rollback").build()),
+            }
+            context(Element::image("https://example.com/bot.png", "bot"), Text::plain("via CI")),
+        ])
+        .build_request()
+        .to_json()
+        .expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    let blocks = value["blocks"].as_array().expect("blocks");
+    let kinds: Vec<&str> = blocks.iter().map(|b| b["type"].as_str().unwrap()).collect();
+    assert_eq!(
+        kinds,
+        ["header", "section", "section", "actions", "context"]
+    );
+    assert_eq!(blocks[2]["text"]["text"], "*worker* down");
+    assert_eq!(blocks[2]["accessory"]["action_id"], "logs-worker");
+    assert_eq!(blocks[4]["elements"][1]["type"], "plain_text");
+}
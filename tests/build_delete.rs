@@ -2,13 +2,14 @@
 
 use slaq::api::chat::delete::Delete;
 use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
 
 #[test]
 fn build_delete_minimal() {
     let channel = "C123".to_string();
-    let ts = "1727612345.000200".to_string();
+    let ts = Ts::new(1_727_612_345, 200);
 
-    let payload = Delete::new(channel.clone(), ts.clone());
+    let payload = Delete::new(channel.clone(), ts);
     let req = payload.build_request();
     assert_eq!(req.path, "/chat.delete");
     assert!(matches!(req.method, HttpMethod::Post));
@@ -25,7 +26,7 @@ fn build_delete_minimal() {
 
 #[test]
 fn from_method_into_request_delete() {
-    let method = Delete::new("C999".to_string(), "1727612345.000300".to_string()).as_user(true);
+    let method = Delete::new("C999".to_string(), Ts::new(1_727_612_345, 300)).as_user(true);
     let req: SlackRequest<Delete> = method.into();
     assert_eq!(req.path, "/chat.delete");
     let json = req.to_json().expect("json");
@@ -0,0 +1,25 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::chat::get_permalink::GetPermalink;
+use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
+
+#[test]
+fn build_get_permalink_minimal() {
+    let payload = GetPermalink::new("C123", Ts::new(1_405_894_322, 2768));
+    let req = payload.build_request();
+    assert_eq!(req.path, "/chat.getPermalink");
+    assert!(matches!(req.method, HttpMethod::Post));
+    assert!(matches!(req.encoding, Encoding::Json));
+
+    let body = req.to_json().expect("json");
+    assert!(body.contains("\"channel\":\"C123\""));
+    assert!(body.contains("\"message_ts\":\"1405894322.002768\""));
+}
+
+#[test]
+fn from_method_into_request_get_permalink() {
+    let method = GetPermalink::new("C999", Ts::new(1_405_894_322, 2769));
+    let req: SlackRequest<GetPermalink> = method.into();
+    assert_eq!(req.path, "/chat.getPermalink");
+}
@@ -1,8 +1,11 @@
 #![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 
-use slaq::api::chat::post_message::PostMessage;
+use slaq::api::attachment::{Attachment, AttachmentField};
+use slaq::api::chat::post_message::{PostMessage, PostMessageResponse};
+use slaq::api::metadata::Metadata;
 use slaq::blocks;
 use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
 
 #[test]
 fn build_post_message_minimal() {
@@ -51,3 +54,96 @@ fn from_method_into_request() {
     let json = req.to_json().expect("json");
     assert!(json.contains("\"text\":\"hi\""));
 }
+
+#[test]
+fn decode_post_message_response() {
+    let raw = r#"{
+        "channel": "C123",
+        "ts": "1503435956.000247",
+        "message": {
+            "type": "message",
+            "text": "hello",
+            "bot_id": "B123",
+            "ts": "1503435956.000247"
+        }
+    }"#;
+    let resp: PostMessageResponse = serde_json::from_str(raw).expect("decode");
+    assert_eq!(resp.channel, "C123");
+    assert_eq!(resp.ts, "1503435956.000247");
+    assert_eq!(resp.message.text.as_deref(), Some("hello"));
+    assert_eq!(resp.message.ts, Some(resp.ts));
+}
+
+#[test]
+fn build_post_message_with_metadata() {
+    #[derive(Clone, serde::Serialize)]
+    struct Incident {
+        id: &'static str,
+    }
+
+    let message = PostMessage::new("C1")
+        .text("Incident opened")
+        .metadata(Metadata::new("incident_created", Incident { id: "INC-42" }));
+    let expected =
+        r#""metadata":{"event_type":"incident_created","event_payload":{"id":"INC-42"}}"#;
+    let json = message.clone().build_request().to_json().expect("json");
+    assert!(json.contains(expected));
+
+    // Typed metadata carries over when the message is turned into an update.
+    let update = message.into_update(Ts::new(1_503_435_956, 247));
+    let json = update.build_request().to_json().expect("json");
+    assert!(json.contains(expected));
+}
+
+#[test]
+fn build_post_message_with_attachments() {
+    let json = PostMessage::new("C1")
+        .attachments(vec![
+            Attachment::new()
+                .color("danger")
+                .title("Build failed")
+                .title_link("https://ci.example.com/1")
+                .fields(vec![AttachmentField::new("Branch", "main").short(true)])
+                .footer("ci"),
+        ])
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""attachments":[{"color":"danger","title":"Build failed""#));
+    assert!(json.contains(r#""fields":[{"title":"Branch","value":"main","short":true}]"#));
+}
+
+#[test]
+fn text_from_blocks_fills_unset_text_when_built() {
+    let blocks = vec![
+        blocks::Header::new("Deploy finished").build(),
+        blocks::Section::new()
+            .text(blocks::Text::mrkdwn("All *green*"))
+            .build(),
+    ];
+    let json = PostMessage::new("C1")
+        .blocks(blocks.clone())
+        .text_from_blocks(true)
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""text":"*Deploy finished*\nAll *green*""#));
+    assert!(!json.contains("text_from_blocks"));
+
+    let json = PostMessage::new("C1")
+        .blocks(blocks.clone())
+        .text("Custom fallback")
+        .text_from_blocks(true)
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""text":"Custom fallback""#));
+
+    let json = PostMessage::new("C1")
+        .blocks(blocks)
+        .build_request()
+        .to_json()
+        .expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    assert!(value.get("text").is_none());
+}
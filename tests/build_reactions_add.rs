@@ -0,0 +1,26 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::reactions::add::Add;
+use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
+
+#[test]
+fn build_reactions_add_minimal() {
+    let payload = Add::new("C123", "thumbsup", Ts::new(1_405_894_322, 2768));
+    let req = payload.build_request();
+    assert_eq!(req.path, "/reactions.add");
+    assert!(matches!(req.method, HttpMethod::Post));
+    assert!(matches!(req.encoding, Encoding::Json));
+
+    let body = req.to_json().expect("json");
+    assert!(body.contains("\"channel\":\"C123\""));
+    assert!(body.contains("\"name\":\"thumbsup\""));
+    assert!(body.contains("\"timestamp\":\"1405894322.002768\""));
+}
+
+#[test]
+fn from_method_into_request_reactions_add() {
+    let method = Add::new("C999", "tada", Ts::new(1_405_894_322, 2769));
+    let req: SlackRequest<Add> = method.into();
+    assert_eq!(req.path, "/reactions.add");
+}
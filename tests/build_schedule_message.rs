@@ -1,7 +1,10 @@
 #![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 
-use slaq::api::chat::schedule_message::ScheduleMessage;
+use std::time::{Duration, SystemTime};
+
+use slaq::api::chat::schedule_message::{ScheduleMessage, ScheduleMessageResponse};
 use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::schedule::{PostAt, PostAtError};
 
 #[test]
 fn build_schedule_message_minimal() {
@@ -29,3 +32,30 @@ fn from_method_into_request_schedule_message() {
     let json = req.to_json().expect("json");
     assert!(json.contains("\"text\":\"hi later\""));
 }
+
+#[test]
+fn post_at_accepts_system_time_and_checks_window() {
+    let soon = ScheduleMessage::new("C1", SystemTime::now() + Duration::from_hours(1));
+    assert!(soon.validate_post_at().is_ok());
+    let json = soon.build_request().to_json().expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    assert!(value["post_at"].is_i64());
+
+    let late = ScheduleMessage::new("C1", PostAt::in_days(121));
+    assert!(matches!(
+        late.validate_post_at(),
+        Err(PostAtError::TooFarAhead(_))
+    ));
+    let past = ScheduleMessage::new("C1", 299_876_400i64);
+    assert_eq!(
+        past.validate_post_at(),
+        Err(PostAtError::NotInFuture(299_876_400))
+    );
+}
+
+#[test]
+fn decode_schedule_message_response_post_at() {
+    let raw = r#"{"channel":"C1","scheduled_message_id":"Q1298393284","post_at":"1562180400"}"#;
+    let resp: ScheduleMessageResponse = serde_json::from_str(raw).expect("decode");
+    assert_eq!(resp.post_at, PostAt::from_unix(1_562_180_400));
+}
@@ -1,15 +1,20 @@
 #![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 
-use slaq::api::chat::unfurl::Unfurl;
+use slaq::api::attachment::Attachment;
+use slaq::api::chat::unfurl::{Unfurl, UnfurlSource, UnfurlTarget};
+use slaq::blocks;
 use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
 
 #[test]
 fn build_unfurl_minimal() {
     let channel = "C123".to_string();
-    let ts = "1727612345.000200".to_string();
-    let unfurls = "{\"https://example.com\":{\"blocks\":[...]}}".to_string();
+    let ts = Ts::new(1_727_612_345, 200);
 
-    let payload = Unfurl::new(channel.clone(), ts.clone(), unfurls.clone());
+    let payload = Unfurl::new(UnfurlTarget::message(channel.clone(), ts)).unfurl(
+        "https://example.com",
+        vec![blocks::Markdown::new("Example").build()],
+    );
     let req = payload.build_request();
     assert_eq!(req.path, "/chat.unfurl");
     assert!(matches!(req.method, HttpMethod::Post));
@@ -20,19 +25,40 @@ fn build_unfurl_minimal() {
     assert!(body.contains(&format!("\"{channel}\"")));
     assert!(body.contains("\"ts\""));
     assert!(body.contains(&format!("\"{ts}\"")));
-    assert!(body.contains("\"unfurls\""));
+    let json: serde_json::Value = serde_json::from_str(&body).expect("value");
+    assert_eq!(
+        json["unfurls"]["https://example.com"]["blocks"][0]["text"],
+        "Example"
+    );
+}
+
+#[test]
+fn build_unfurl_by_unfurl_id_with_attachment() {
+    let payload = Unfurl::new(UnfurlTarget::unfurl_id(
+        "Uxxxxxxx-909b5454-75f8-4ac4-b325-1b40e230bbd8",
+        UnfurlSource::Composer,
+    ))
+    .unfurl(
+        "https://example.com/issue/1",
+        Attachment::new().title("Issue 1").color("good"),
+    );
+    let body = payload.build_request().to_json().expect("json");
+    assert!(body.contains(
+        r#""unfurl_id":"Uxxxxxxx-909b5454-75f8-4ac4-b325-1b40e230bbd8","source":"composer""#
+    ));
+    assert!(!body.contains("\"channel\""));
+    assert!(body.contains(
+        r#""unfurls":{"https://example.com/issue/1":{"color":"good","title":"Issue 1"}}"#
+    ));
 }
 
 #[test]
 fn from_method_into_request_unfurl() {
-    let method = Unfurl::new(
-        "C999".to_string(),
-        "1727612345.000300".to_string(),
-        "{}".to_string(),
-    )
-    .user_auth_required(true);
+    let method = Unfurl::new(UnfurlTarget::message("C999", Ts::new(1_727_612_345, 300)))
+        .user_auth_required(true);
     let req: SlackRequest<Unfurl> = method.into();
     assert_eq!(req.path, "/chat.unfurl");
     let json = req.to_json().expect("json");
     assert!(json.contains("\"user_auth_required\":true"));
+    assert!(json.contains("\"unfurls\":{}"));
 }
@@ -1,15 +1,17 @@
 #![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
 
-use slaq::api::chat::update::Update;
+use slaq::api::chat::update::{Update, UpdateResponse};
+use slaq::api::metadata::Metadata;
 use slaq::blocks;
 use slaq::client::{Encoding, HttpMethod, SlackRequest};
+use slaq::id::Ts;
 
 #[test]
 fn build_update_minimal() {
     let channel = "C123".to_string();
-    let ts = "1405894322.002768".to_string();
+    let ts = Ts::new(1_405_894_322, 2768);
 
-    let payload = Update::new(channel.clone(), ts.clone());
+    let payload = Update::new(channel.clone(), ts);
     let req = payload.build_request();
     assert_eq!(req.path, "/chat.update");
     assert!(matches!(req.method, HttpMethod::Post));
@@ -24,7 +26,7 @@ fn build_update_minimal() {
 
 #[test]
 fn build_update_with_options() {
-    let payload = Update::new("C999".to_string(), "1405894322.002769".to_string())
+    let payload = Update::new("C999".to_string(), Ts::new(1_405_894_322, 2769))
         .text("updated")
         .link_names(true)
         .blocks(vec![
@@ -37,11 +39,59 @@ fn build_update_with_options() {
     assert!(json.contains("\"link_names\":true"));
 }
 
+#[test]
+fn build_update_removing_metadata() {
+    let json = Update::new("C123", Ts::new(1_405_894_322, 2768))
+        .text("resolved")
+        .metadata(Metadata::empty())
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""metadata":{}"#));
+}
+
 #[test]
 fn from_method_into_request_update() {
-    let method = Update::new("C999".to_string(), "1405894322.002770".to_string()).text("hi");
+    let method = Update::new("C999".to_string(), Ts::new(1_405_894_322, 2770)).text("hi");
     let req: SlackRequest<Update> = method.into();
     assert_eq!(req.path, "/chat.update");
     let json = req.to_json().expect("json");
     assert!(json.contains("\"text\":\"hi\""));
 }
+
+#[test]
+fn decode_update_response() {
+    let raw = r#"{
+        "channel": "C123",
+        "ts": "1401383885.000061",
+        "text": "Updated text you carefully authored",
+        "message": {"text": "Updated text you carefully authored", "user": "U34567890"}
+    }"#;
+    let resp: UpdateResponse = serde_json::from_str(raw).expect("decode");
+    assert_eq!(resp.ts, "1401383885.000061");
+    assert_eq!(resp.message.unwrap().user.unwrap(), "U34567890");
+}
+
+#[test]
+fn received_blocks_can_be_edited_and_reposted() {
+    let raw = r#"{
+        "channel": "C123",
+        "ts": "1401383885.000061",
+        "message": {"ts": "1401383885.000061", "blocks": [
+            {"type": "section", "block_id": "status", "text": {"type": "mrkdwn", "text": "Deploying...", "verbatim": false}},
+            {"type": "divider", "block_id": "d1"}
+        ]}
+    }"#;
+    let resp: UpdateResponse = serde_json::from_str(raw).expect("decode");
+    let mut blocks = resp.message.unwrap().blocks.unwrap();
+    if let blocks::Block::Section(section) = &mut blocks[0] {
+        section.text = Some(blocks::Text::mrkdwn("Deployed :white_check_mark:"));
+    }
+    let json = Update::new(resp.channel, resp.ts)
+        .blocks(blocks)
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""text":{"type":"mrkdwn","text":"Deployed :white_check_mark:"}"#));
+    assert!(json.contains(r#""block_id":"d1""#));
+}
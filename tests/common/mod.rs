@@ -0,0 +1,66 @@
+//! A mock Slack client shared by the integration tests.
+
+#![allow(dead_code, clippy::must_use_candidate, clippy::missing_panics_doc)]
+
+use std::cell::{Ref, RefCell};
+
+use serde_json::{Value, json};
+use slaq::client::{Execute, SlackMethod};
+
+type Handler = Box<dyn Fn(&Value) -> Value>;
+
+/// Records every executed request and answers with a canned response per path.
+///
+/// Paths without a response answer `{}`.
+#[derive(Default)]
+pub struct MockSlack {
+    responses: Vec<(&'static str, Handler)>,
+    calls: RefCell<Vec<(&'static str, Value)>>,
+}
+
+impl MockSlack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Answers requests to `path` with `respond(body)`.
+    #[must_use]
+    pub fn on(mut self, path: &'static str, respond: impl Fn(&Value) -> Value + 'static) -> Self {
+        self.responses.push((path, Box::new(respond)));
+        self
+    }
+
+    /// Every request so far, as `(path, body)`.
+    pub fn calls(&self) -> Ref<'_, Vec<(&'static str, Value)>> {
+        self.calls.borrow()
+    }
+
+    /// The bodies of the requests to `path`.
+    pub fn bodies(&self, path: &str) -> Vec<Value> {
+        self.calls()
+            .iter()
+            .filter(|(p, _)| *p == path)
+            .map(|(_, body)| body.clone())
+            .collect()
+    }
+
+    /// The number of requests to `path`.
+    pub fn count(&self, path: &str) -> usize {
+        self.calls().iter().filter(|(p, _)| *p == path).count()
+    }
+}
+
+impl Execute for MockSlack {
+    type Error = serde_json::Error;
+
+    fn execute<M: SlackMethod>(&self, method: M) -> Result<M::Response, Self::Error> {
+        let body = serde_json::to_value(method.into_body())?;
+        let resp = self
+            .responses
+            .iter()
+            .find(|(path, _)| *path == M::PATH)
+            .map_or_else(|| json!({}), |(_, respond)| respond(&body));
+        self.calls.borrow_mut().push((M::PATH, body));
+        serde_json::from_value(resp)
+    }
+}
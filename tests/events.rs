@@ -0,0 +1,108 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::chat::unfurl::{UnfurlSource, UnfurlTarget};
+use slaq::events::{Envelope, Event, MessageSubtype};
+use slaq::id::Ts;
+
+fn callback(event: &str) -> Envelope {
+    let body = format!(
+        r#"{{
+            "token": "XXYYZZ",
+            "team_id": "T123",
+            "api_app_id": "A123",
+            "event": {event},
+            "type": "event_callback",
+            "event_id": "Ev08MFMKH6",
+            "event_time": 1234567890,
+            "event_context": "EC123",
+            "authorizations": [
+                {{"enterprise_id": "E123", "team_id": "T123", "user_id": "U0BOT", "is_bot": true, "is_enterprise_install": false}}
+            ]
+        }}"#
+    );
+    serde_json::from_str(&body).unwrap()
+}
+
+fn event(json: &str) -> Event {
+    let Envelope::EventCallback(callback) = callback(json) else {
+        panic!("expected event_callback");
+    };
+    callback.event
+}
+
+#[test]
+fn event_callback_carries_metadata() {
+    let Envelope::EventCallback(callback) = callback(r#"{"type": "app_uninstalled"}"#) else {
+        panic!("expected event_callback");
+    };
+    assert_eq!(callback.event_id, "Ev08MFMKH6");
+    assert_eq!(callback.event_time, 1_234_567_890);
+    assert_eq!(callback.team_id, "T123");
+    assert_eq!(callback.authorizations[0].user_id, "U0BOT");
+    assert!(callback.authorizations[0].is_bot);
+    assert!(matches!(callback.event, Event::AppUninstalled));
+}
+
+#[test]
+fn thread_reply_from_a_bot() {
+    let Event::Message(message) = event(
+        r#"{"type": "message", "subtype": "bot_message", "channel": "C1", "channel_type": "channel",
+            "bot_id": "B1", "text": "done", "ts": "1700000001.000200", "thread_ts": "1700000000.000100",
+            "event_ts": "1700000001.000200"}"#,
+    ) else {
+        panic!("expected message");
+    };
+    assert_eq!(message.subtype, Some(MessageSubtype::BotMessage));
+    assert!(message.is_bot());
+    assert!(message.is_thread_reply());
+    assert_eq!(message.thread_ts, Some(Ts::new(1_700_000_000, 100)));
+}
+
+#[test]
+fn link_shared_targets_unfurl() {
+    let Event::LinkShared(shared) = event(
+        r#"{"type": "link_shared", "channel": "COMPOSER", "user": "U1", "message_ts": "Uxxx-123",
+            "unfurl_id": "C123456.123456789.987501", "source": "composer", "event_ts": "123456621.1855",
+            "links": [{"domain": "example.com", "url": "https://example.com/12345"}]}"#,
+    ) else {
+        panic!("expected link_shared");
+    };
+    assert_eq!(shared.links[0].domain, "example.com");
+    assert!(matches!(
+        shared.unfurl_target(),
+        Some(UnfurlTarget::UnfurlId {
+            source: UnfurlSource::Composer,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn other_events_are_typed_or_kept() {
+    let kinds: Vec<String> = [
+        r#"{"type": "member_joined_channel", "user": "U1", "channel": "C1", "channel_type": "C", "team": "T1"}"#,
+        r#"{"type": "channel_created", "channel": {"id": "C1", "name": "fun", "created": 1360782804, "creator": "U1"}}"#,
+        r#"{"type": "app_home_opened", "user": "U1", "channel": "D1", "tab": "home", "event_ts": "1515449522.000016"}"#,
+        r#"{"type": "team_join", "user": {"id": "U9", "name": "new", "tz_offset": -18000}}"#,
+        r#"{"type": "tokens_revoked", "tokens": {"oauth": ["U1"], "bot": ["U0BOT"]}}"#,
+        r#"{"type": "pin_added", "user": "U1"}"#,
+    ]
+    .iter()
+    .map(|json| {
+        let event = event(json);
+        let typed = !matches!(event, Event::Unknown(_));
+        format!("{}:{typed}", event.kind())
+    })
+    .collect();
+    assert_eq!(
+        kinds,
+        [
+            "member_joined_channel:true",
+            "channel_created:true",
+            "app_home_opened:true",
+            "team_join:true",
+            "tokens_revoked:true",
+            "pin_added:false",
+        ]
+    );
+}
@@ -0,0 +1,49 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::chat::post_ephemeral::PostEphemeral;
+use slaq::api::chat::post_message::{PostMessage, PostMessageResponse};
+use slaq::api::chat::update::Update;
+use slaq::id::{ChannelId, Ts, UserId};
+
+#[test]
+fn typed_ids_feed_existing_builders() {
+    let channel = ChannelId::new("C123");
+    let parent: Ts = "1405894322.002768".parse().unwrap();
+    let json = PostMessage::new(&channel)
+        .text("reply")
+        .thread_ts(parent)
+        .build_request()
+        .to_json()
+        .expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    assert_eq!(value["channel"], "C123");
+    assert_eq!(value["thread_ts"], "1405894322.002768");
+
+    let json = PostEphemeral::new(&channel, UserId::new("U1"))
+        .text("only you")
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""channel":"C123","user":"U1""#));
+
+    let json = Update::new(channel, parent)
+        .text(format!("edited by {}", UserId::new("U1")))
+        .build_request()
+        .to_json()
+        .expect("json");
+    assert!(json.contains(r#""ts":"1405894322.002768""#));
+}
+
+#[test]
+fn responses_carry_typed_ids() {
+    let raw = r#"{"channel":"C1","ts":"1405894322.002768","message":{"ts":"1405894322.002768","user":"U1"}}"#;
+    let resp: PostMessageResponse = serde_json::from_str(raw).expect("decode");
+    let channel: ChannelId = resp.channel;
+    let ts: Ts = resp.ts;
+    let user: Option<UserId> = resp.message.user;
+    assert_eq!(channel, "C1");
+    assert!(ts < Ts::now());
+    assert_eq!(ts, "1405894322.002768");
+    assert_eq!(resp.message.ts, Some(ts));
+    assert_eq!(user, Some(UserId::new("U1")));
+}
@@ -0,0 +1,107 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+mod common;
+
+use serde_json::json;
+use slaq::api::chat::handle::SendError;
+use slaq::api::chat::payload::ContentError;
+use slaq::api::chat::post_message::PostMessage;
+use slaq::id::Ts;
+
+use common::MockSlack;
+
+fn slack() -> MockSlack {
+    MockSlack::new()
+        .on("/chat.postMessage", |body| {
+            let ts = if body.get("thread_ts").is_some() {
+                "2.000000"
+            } else {
+                "1.000000"
+            };
+            json!({"channel": "C1", "ts": ts, "message": {"ts": ts}})
+        })
+        .on(
+            "/chat.update",
+            |_| json!({"channel": "C1", "ts": "1.000000"}),
+        )
+        .on(
+            "/chat.delete",
+            |_| json!({"channel": "C1", "ts": "1.000000"}),
+        )
+        .on(
+            "/chat.getPermalink",
+            |_| json!({"channel": "C1", "permalink": "https://example.slack.com/p1"}),
+        )
+}
+
+#[test]
+fn handle_follow_ups_target_the_posted_message() {
+    let client = slack();
+    let handle = PostMessage::new("C1").text("hello").post(&client).unwrap();
+    assert_eq!(handle.channel, "C1");
+    assert_eq!(handle.ts, "1.000000");
+
+    handle.update(|u| u.text("edited")).unwrap();
+    handle.react("tada").unwrap();
+    let link = handle.permalink().unwrap();
+    assert_eq!(link, "https://example.slack.com/p1");
+    handle.delete().unwrap();
+
+    let calls = client.calls();
+    let paths: Vec<_> = calls.iter().map(|(p, _)| *p).collect();
+    assert_eq!(
+        paths,
+        [
+            "/chat.postMessage",
+            "/chat.update",
+            "/reactions.add",
+            "/chat.getPermalink",
+            "/chat.delete"
+        ]
+    );
+    assert_eq!(calls[1].1["ts"], "1.000000");
+    assert_eq!(calls[1].1["text"], "edited");
+    assert_eq!(calls[2].1["timestamp"], "1.000000");
+    assert_eq!(calls[2].1["name"], "tada");
+    assert_eq!(calls[3].1["message_ts"], "1.000000");
+    assert_eq!(calls[4].1["channel"], "C1");
+}
+
+#[test]
+fn replies_stay_in_the_parent_thread() {
+    let client = slack();
+    let parent = PostMessage::new("C1").text("parent").post(&client).unwrap();
+    let reply = parent.reply(|m| m.text("first")).unwrap();
+    assert_eq!(reply.ts, "2.000000");
+    assert_eq!(reply.thread_ts, Some(Ts::new(1, 0)));
+
+    reply.reply(|m| m.text("second")).unwrap();
+
+    let calls = client.calls();
+    assert_eq!(calls[1].1["thread_ts"], "1.000000");
+    assert_eq!(calls[2].1["thread_ts"], "1.000000");
+}
+
+#[test]
+fn invalid_content_is_rejected_before_sending() {
+    let client = slack();
+    assert!(matches!(
+        PostMessage::new("C1").post(&client),
+        Err(SendError::Content(ContentError::Empty))
+    ));
+
+    let handle = PostMessage::new("C1").text("hello").post(&client).unwrap();
+    let err = handle
+        .update(|u| u.text("edited").markdown_text("**edited**"))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        SendError::Content(ContentError::MarkdownWithOtherContent)
+    ));
+    assert!(matches!(
+        handle.reply(|m| m),
+        Err(SendError::Content(ContentError::Empty))
+    ));
+    assert_eq!(client.count("/chat.postMessage"), 1);
+    assert_eq!(client.count("/chat.update"), 0);
+}
@@ -0,0 +1,236 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::attachment::Attachment;
+use slaq::api::chat::me_message::MeMessage;
+use slaq::api::chat::payload::{ContentError, MessageBody, MessageContent, MessagePayload};
+use slaq::api::chat::post_message::PostMessage;
+use slaq::api::chat::update::Update;
+use slaq::blocks;
+use slaq::id::Ts;
+use slaq::schedule::{PostAt, PostAtError};
+
+fn render<P: MessagePayload>(payload: P, name: &str) -> Result<P, ContentError> {
+    payload.with_content(
+        MessageContent::new()
+            .text(format!("Hello {name}"))
+            .blocks(vec![
+                blocks::Markdown::new(format!("*Hello {name}*")).build(),
+            ])
+            .link_names(true),
+    )
+}
+
+#[test]
+fn generic_code_sets_content_on_any_payload() {
+    let post = render(PostMessage::new("C1"), "post").unwrap();
+    let update = render(Update::new("C1", Ts::new(1, 0)), "update").unwrap();
+
+    let post_json = serde_json::to_value(&post).unwrap();
+    assert_eq!(post_json["text"], "Hello post");
+    assert_eq!(post_json["link_names"], true);
+    assert_eq!(post_json["blocks"][0]["text"], "*Hello post*");
+
+    let update_json = serde_json::to_value(&update).unwrap();
+    assert_eq!(update_json["text"], "Hello update");
+    assert_eq!(update_json["ts"], "1.000000");
+}
+
+#[test]
+fn me_message_keeps_text_only() {
+    let me = MeMessage::new("C1", "old")
+        .with_content(MessageContent::new().text("Hello me"))
+        .unwrap();
+    assert_eq!(me.text, "Hello me");
+    assert_eq!(me.content().text.as_deref(), Some("Hello me"));
+    assert!(me.content().blocks.is_none());
+}
+
+#[test]
+fn me_message_rejects_content_it_cannot_carry() {
+    let mut me = MeMessage::new("C1", "old");
+    assert_eq!(
+        me.set_content(MessageContent::new().text("hi").blocks(vec![])),
+        Err(ContentError::Unsupported("blocks"))
+    );
+    assert_eq!(
+        me.set_content(MessageContent::new().markdown_text("**hi**")),
+        Err(ContentError::Unsupported("markdown_text"))
+    );
+    assert_eq!(
+        me.set_content(MessageContent::new()),
+        Err(ContentError::Empty)
+    );
+    assert_eq!(me.text, "old");
+
+    let err = render(MeMessage::new("C1", "old"), "me").unwrap_err();
+    assert_eq!(err.to_string(), "blocks cannot be sent with this method");
+}
+
+#[test]
+fn content_delivers_in_any_mode() {
+    let content = MessageContent::new().markdown_text("**hi**");
+    let post = content.clone().post_message("C1");
+    let eph = content.clone().post_ephemeral("C1", "U1");
+    let post_at = PostAt::in_hours(1);
+    let sched = content.clone().schedule_message("C1", post_at).unwrap();
+    let update = content.update("C1", Ts::new(1, 0));
+
+    assert_eq!(post.markdown_text.as_deref(), Some("**hi**"));
+    assert_eq!(eph.user, "U1");
+    assert_eq!(eph.markdown_text.as_deref(), Some("**hi**"));
+    assert_eq!(sched.post_at, post_at);
+    assert_eq!(sched.markdown_text.as_deref(), Some("**hi**"));
+    assert_eq!(update.markdown_text.as_deref(), Some("**hi**"));
+}
+
+#[test]
+fn scheduling_content_checks_the_window() {
+    let content = MessageContent::new().text("hi");
+    let past = PostAt::from_unix(1_700_000_000);
+    assert_eq!(
+        content.clone().schedule_message("C1", past).unwrap_err(),
+        PostAtError::NotInFuture(1_700_000_000)
+    );
+    assert!(matches!(
+        content.schedule_message("C1", PostAt::in_days(121)),
+        Err(PostAtError::TooFarAhead(_))
+    ));
+}
+
+#[test]
+fn post_message_converts_to_other_modes() {
+    let post = PostMessage::new("C1")
+        .text("hi")
+        .thread_ts(Ts::new(1, 0))
+        .username("bot")
+        .unfurl_links(false);
+
+    let sched = post
+        .clone()
+        .into_schedule_message(PostAt::in_days(1))
+        .unwrap();
+    assert_eq!(sched.channel, "C1");
+    assert_eq!(sched.text.as_deref(), Some("hi"));
+    assert_eq!(sched.thread_ts, Some(Ts::new(1, 0)));
+    assert_eq!(sched.unfurl_links, Some(false));
+
+    assert_eq!(
+        post.clone().into_post_ephemeral("U1").unwrap_err(),
+        ContentError::Unsupported("unfurl_links")
+    );
+    let mut plain = post.clone();
+    plain.unfurl_links = None;
+    let eph = plain.into_post_ephemeral("U1").unwrap();
+    assert_eq!(eph.user, "U1");
+    assert_eq!(eph.username.as_deref(), Some("bot"));
+    assert_eq!(eph.text.as_deref(), Some("hi"));
+
+    let update = post.into_update(Ts::new(2, 0));
+    assert_eq!(update.ts, "2.000000");
+    assert_eq!(update.text.as_deref(), Some("hi"));
+}
+
+#[test]
+fn conversions_check_post_at_and_fill_text_from_blocks() {
+    let post = PostMessage::new("C1")
+        .blocks(vec![
+            blocks::Header::new(blocks::PlainText::new("Deployed")).build(),
+        ])
+        .text_from_blocks(true);
+    assert!(matches!(
+        post.clone().into_schedule_message(PostAt::in_days(121)),
+        Err(PostAtError::TooFarAhead(_))
+    ));
+
+    let eph = post.into_post_ephemeral("U1").unwrap();
+    assert_eq!(eph.text.as_deref(), Some("*Deployed*"));
+}
+
+#[test]
+fn validate_rejects_markdown_with_other_content() {
+    let ok = PostMessage::new("C1").markdown_text("**hi**");
+    assert_eq!(ok.validate(), Ok(()));
+
+    let with_text = PostMessage::new("C1").markdown_text("**hi**").text("hi");
+    assert_eq!(
+        with_text.validate(),
+        Err(ContentError::MarkdownWithOtherContent)
+    );
+
+    let with_blocks = Update::new("C1", Ts::new(1, 0))
+        .markdown_text("**hi**")
+        .blocks(vec![blocks::Divider::new().build()]);
+    assert_eq!(
+        with_blocks.validate(),
+        Err(ContentError::MarkdownWithOtherContent)
+    );
+}
+
+#[test]
+fn validate_rejects_empty_content() {
+    assert_eq!(PostMessage::new("C1").validate(), Err(ContentError::Empty));
+    assert_eq!(
+        MeMessage::new("C1", "").validate(),
+        Err(ContentError::Empty)
+    );
+    assert_eq!(MessageContent::new().validate(), Err(ContentError::Empty));
+}
+
+#[test]
+fn blocks_with_fallback_text_is_valid() {
+    let post = PostMessage::new("C1")
+        .text("fallback")
+        .blocks(vec![blocks::Divider::new().build()]);
+    assert_eq!(post.validate(), Ok(()));
+}
+
+#[test]
+fn with_body_replaces_conflicting_fields() {
+    let post = PostMessage::new("C1")
+        .text("old")
+        .link_names(true)
+        .with_body(MessageBody::Markdown("**new**".into()))
+        .unwrap();
+    assert!(post.text.is_none());
+    assert_eq!(post.markdown_text.as_deref(), Some("**new**"));
+    assert_eq!(post.link_names, Some(true));
+    assert_eq!(post.validate(), Ok(()));
+
+    let post = post
+        .with_body(MessageBody::Blocks {
+            blocks: vec![blocks::Divider::new().build()],
+            fallback: Some("divider".into()),
+        })
+        .unwrap();
+    assert!(post.markdown_text.is_none());
+    assert_eq!(post.text.as_deref(), Some("divider"));
+    assert_eq!(post.validate(), Ok(()));
+
+    let content: MessageContent = MessageBody::Text("plain".into()).into();
+    assert_eq!(content.text.as_deref(), Some("plain"));
+}
+
+#[test]
+fn attachments_alone_are_valid_content() {
+    let post = PostMessage::new("C1").attachments(vec![Attachment::new().color("good").text("ok")]);
+    assert_eq!(post.validate(), Ok(()));
+
+    let eph = post.into_post_ephemeral("U1").unwrap();
+    assert_eq!(eph.attachments.as_ref().map(Vec::len), Some(1));
+}
+
+#[test]
+fn validate_reports_invalid_blocks_with_paths() {
+    let msg = PostMessage::new("C1").blocks(vec![
+        blocks::Divider::new().block_id("same").build(),
+        blocks::Divider::new().block_id("same").build(),
+    ]);
+    let Err(ContentError::InvalidBlocks(errors)) = msg.validate() else {
+        panic!("expected invalid blocks");
+    };
+    assert_eq!(errors.0[0].path, "blocks[1].block_id");
+    assert_eq!(
+        errors.to_string(),
+        "blocks[1].block_id: block_id `same` is used by another block"
+    );
+}
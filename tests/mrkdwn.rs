@@ -0,0 +1,62 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+use slaq::api::chat::payload::MessageContent;
+use slaq::api::chat::post_message::PostMessage;
+use slaq::api::chat::update::Update;
+use slaq::blocks::{self, Text};
+use slaq::id::Ts;
+use slaq::mrkdwn::{self, Broadcast, Mrkdwn};
+
+#[test]
+fn mrkdwn_feeds_text_setters_without_injection() {
+    let user_input = "<!channel> pls look";
+    let text = mrkdwn::broadcast(Broadcast::Here)
+        + " "
+        + mrkdwn::user("U123")
+        + " reported: "
+        + mrkdwn::italic(user_input);
+    let json = PostMessage::new("C1")
+        .mrkdwn_text(text.clone())
+        .blocks(vec![blocks::Section::new().text(text).build()])
+        .build_request()
+        .to_json()
+        .expect("json");
+    let value: serde_json::Value = serde_json::from_str(&json).expect("parse");
+    let expected = "<!here> <@U123> reported: _&lt;!channel&gt; pls look_";
+    assert_eq!(value["text"], expected);
+    assert_eq!(value["blocks"][0]["text"]["type"], "mrkdwn");
+    assert_eq!(value["blocks"][0]["text"]["text"], expected);
+}
+
+#[test]
+fn mrkdwn_text_setters_escape_plain_strings() {
+    let post = PostMessage::new("C1").mrkdwn_text("<!channel> & co");
+    assert_eq!(post.text.as_deref(), Some("&lt;!channel&gt; &amp; co"));
+
+    let update = Update::new("C1", Ts::new(1, 0)).mrkdwn_text(mrkdwn::bold("done"));
+    assert_eq!(update.text.as_deref(), Some("*done*"));
+
+    let content = MessageContent::new().mrkdwn_text("a <b>");
+    assert_eq!(content.text.as_deref(), Some("a &lt;b&gt;"));
+}
+
+#[test]
+fn mrkdwn_converts_into_text_objects() {
+    let text: Text = Mrkdwn::from("1 < 2").into();
+    assert!(matches!(text, Text::Mrkdwn(ref t) if t.text == "1 &lt; 2"));
+}
+
+#[test]
+fn built_mrkdwn_parses_back_into_mentions_and_text() {
+    let text = mrkdwn::user("U1")
+        + " shipped "
+        + mrkdwn::link("https://ci.example.com/1", "build <1>")
+        + " to "
+        + mrkdwn::channel("C9");
+    let doc = mrkdwn::Document::parse(text.as_str());
+    assert_eq!(doc.user_ids(), ["U1"]);
+    assert_eq!(doc.channel_ids(), ["C9"]);
+    assert_eq!(doc.links(), ["https://ci.example.com/1"]);
+    assert_eq!(doc.to_mrkdwn(), text.as_str());
+    assert_eq!(doc.to_plain_text(), "@U1 shipped build <1> to #C9");
+}
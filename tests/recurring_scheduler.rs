@@ -0,0 +1,213 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+mod common;
+
+use std::cell::{Cell, RefCell};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use serde_json::{Value, json};
+use slaq::api::chat::payload::ContentError;
+use slaq::schedule::{
+    PostAt, Recurrence, RecurringJob, Scheduler, SchedulerError, StateError, SyncReport,
+};
+
+use common::MockSlack;
+
+// Friday 2024-03-01 12:00:00 UTC.
+const FRIDAY_NOON: i64 = 1_709_294_400;
+const DAY: i64 = 86_400;
+
+/// A fake Slack workspace that keeps scheduled messages in memory.
+/// Returns the client and the messages currently scheduled.
+fn workspace() -> (MockSlack, Rc<RefCell<Vec<Value>>>) {
+    let scheduled = Rc::new(RefCell::new(Vec::<Value>::new()));
+    let next_id = Cell::new(0);
+    let slack = MockSlack::new()
+        .on("/chat.scheduledMessages.list", {
+            let scheduled = Rc::clone(&scheduled);
+            move |_| {
+                json!({
+                    "scheduled_messages": *scheduled.borrow(),
+                    "response_metadata": {"next_cursor": ""},
+                })
+            }
+        })
+        .on("/chat.scheduleMessage", {
+            let scheduled = Rc::clone(&scheduled);
+            move |body| {
+                next_id.set(next_id.get() + 1);
+                let id = format!("Q{}", next_id.get());
+                scheduled.borrow_mut().push(json!({
+                    "id": id,
+                    "channel_id": body["channel"],
+                    "post_at": body["post_at"],
+                    "date_created": FRIDAY_NOON,
+                    "text": body["text"],
+                }));
+                json!({
+                    "channel": body["channel"],
+                    "scheduled_message_id": id,
+                    "post_at": body["post_at"],
+                })
+            }
+        })
+        .on("/chat.deleteScheduledMessage", {
+            let scheduled = Rc::clone(&scheduled);
+            move |body| {
+                scheduled
+                    .borrow_mut()
+                    .retain(|m| m["id"] != body["scheduled_message_id"]);
+                json!({})
+            }
+        });
+    (slack, scheduled)
+}
+
+fn state_path(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("slaq-scheduler-{name}-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn standup() -> RecurringJob {
+    RecurringJob::new(
+        "standup",
+        "C1",
+        Recurrence::cron("0 9 * * mon-fri".parse().unwrap(), 0),
+    )
+    .text("Standup!")
+}
+
+#[test]
+fn schedules_within_window_and_survives_restart() {
+    let path = state_path("restart");
+    let (slack, pending) = workspace();
+    let now = PostAt::from_unix(FRIDAY_NOON);
+
+    let mut scheduler = Scheduler::open(&path).unwrap().per_job(5);
+    scheduler.add(standup()).unwrap();
+    scheduler
+        .add(
+            RecurringJob::new("launch", "C2", Recurrence::once(FRIDAY_NOON + 200 * DAY))
+                .text("Launch!"),
+        )
+        .unwrap();
+    let report = scheduler.sync(&slack, now).unwrap();
+    assert_eq!(report.scheduled.len(), 5);
+    assert_eq!(
+        report.scheduled[0].post_at,
+        PostAt::from_unix(FRIDAY_NOON + 3 * DAY - 3 * 3600)
+    );
+    assert_eq!(pending.borrow().len(), 5);
+
+    // Reopening from disk schedules nothing new.
+    let mut reopened = Scheduler::open(&path).unwrap().per_job(5);
+    assert_eq!(reopened.jobs().len(), 2);
+    assert_eq!(reopened.scheduled().len(), 5);
+    let report = reopened.sync(&slack, now).unwrap();
+    assert_eq!(report, SyncReport::default());
+    assert_eq!(slack.count("/chat.scheduleMessage"), 5);
+
+    // The far-future job is scheduled once it enters Slack's window.
+    let later = PostAt::from_unix(FRIDAY_NOON + 100 * DAY);
+    let report = reopened.sync(&slack, later).unwrap();
+    assert!(report.scheduled.iter().any(|o| o.job == "launch"));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn adopts_unrecorded_and_cancels_removed() {
+    let path = state_path("adopt");
+    let (slack, pending) = workspace();
+    let now = PostAt::from_unix(FRIDAY_NOON);
+
+    let mut scheduler = Scheduler::open(&path).unwrap().per_job(3);
+    scheduler.add(standup()).unwrap();
+    scheduler.sync(&slack, now).unwrap();
+
+    // Losing the state, as after a crash before saving, doesn't double-schedule.
+    std::fs::remove_file(&path).unwrap();
+    let mut fresh = Scheduler::open(&path).unwrap().per_job(3);
+    fresh.add(standup()).unwrap();
+    let report = fresh.sync(&slack, now).unwrap();
+    assert_eq!(report.adopted.len(), 3);
+    assert!(report.scheduled.is_empty());
+    assert_eq!(pending.borrow().len(), 3);
+
+    // Removing the job cancels its occurrences; other messages stay.
+    pending.borrow_mut().push(json!({
+        "id": "MANUAL",
+        "channel_id": "C1",
+        "post_at": FRIDAY_NOON + DAY,
+        "date_created": FRIDAY_NOON,
+        "text": "not ours",
+    }));
+    fresh.remove("standup").unwrap();
+    let report = fresh.sync(&slack, now).unwrap();
+    assert_eq!(report.cancelled.len(), 3);
+    assert_eq!(pending.borrow().len(), 1);
+    assert!(fresh.scheduled().is_empty());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn reschedules_occurrences_of_edited_jobs() {
+    let path = state_path("edit");
+    let (slack, pending) = workspace();
+    let now = PostAt::from_unix(FRIDAY_NOON);
+
+    let mut scheduler = Scheduler::open(&path).unwrap().per_job(3);
+    scheduler.add(standup()).unwrap();
+    scheduler.sync(&slack, now).unwrap();
+
+    // Same schedule, new text: the old messages must not post.
+    scheduler
+        .add(standup().text("Standup moved to the big room!"))
+        .unwrap();
+    let report = scheduler.sync(&slack, now).unwrap();
+    assert_eq!(report.cancelled.len(), 3);
+    assert_eq!(report.scheduled.len(), 3);
+    assert!(report.adopted.is_empty());
+    let messages = pending.borrow().clone();
+    assert_eq!(messages.len(), 3);
+    assert!(
+        messages
+            .iter()
+            .all(|m| m["text"] == "Standup moved to the big room!")
+    );
+
+    // Nothing changes on the next sync.
+    assert_eq!(scheduler.sync(&slack, now).unwrap(), SyncReport::default());
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn corrupt_state_is_a_state_error() {
+    let path = state_path("corrupt");
+    std::fs::write(&path, "not json").unwrap();
+    let err: StateError = Scheduler::open(&path).unwrap_err();
+    assert!(matches!(err, StateError::Json(_)));
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn jobs_without_content_are_rejected_before_scheduling() {
+    let path = state_path("empty");
+    let (slack, _) = workspace();
+    let mut scheduler = Scheduler::open(&path).unwrap();
+    scheduler
+        .add(RecurringJob::new(
+            "empty",
+            "C1",
+            Recurrence::once(FRIDAY_NOON + DAY),
+        ))
+        .unwrap();
+    let err = scheduler
+        .sync(&slack, PostAt::from_unix(FRIDAY_NOON))
+        .unwrap_err();
+    assert!(matches!(err, SchedulerError::Content(ContentError::Empty)));
+    assert_eq!(slack.count("/chat.scheduleMessage"), 0);
+    let _ = std::fs::remove_file(&path);
+}
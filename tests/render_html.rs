@@ -0,0 +1,37 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+//! Snapshot tests for `blocks::render_html`.
+//!
+//! Each `tests/fixtures/html/<name>.json` holds a block list, and the matching
+//! `<name>.html` its expected rendering. Run with `UPDATE_SNAPSHOTS=1` to
+//! rewrite the `.html` files after an intended change, then review the diff.
+
+use std::fs;
+use std::path::Path;
+
+use slaq::blocks::{self, Block};
+
+#[test]
+fn html_fixtures_match_snapshots() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/html");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let mut checked = 0;
+    for entry in fs::read_dir(&dir).expect("read fixtures") {
+        let path = entry.expect("fixture entry").path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let input = fs::read_to_string(&path).expect("read fixture");
+        let blocks: Vec<Block> = serde_json::from_str(&input).expect("parse fixture blocks");
+        let html = blocks::render_html(&blocks) + "\n";
+        let snapshot = path.with_extension("html");
+        if update {
+            fs::write(&snapshot, &html).expect("write snapshot");
+        } else {
+            let expected = fs::read_to_string(&snapshot).expect("read snapshot");
+            assert_eq!(html, expected, "snapshot mismatch for {}", path.display());
+        }
+        checked += 1;
+    }
+    assert!(checked > 0, "no fixtures found in {}", dir.display());
+}
@@ -0,0 +1,132 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+mod common;
+
+use serde_json::json;
+use slaq::api::chat::scheduled_messages_list::ScheduledMessagesListResponse;
+use slaq::id::Ts;
+use slaq::schedule::{PostAt, PostAtError, RescheduleError, ScheduledFilter, ScheduledMessages};
+
+use common::MockSlack;
+
+/// Serves two pages of scheduled messages.
+fn slack() -> MockSlack {
+    MockSlack::new()
+        .on("/chat.scheduledMessages.list", |body| {
+            if body.get("cursor").is_none() {
+                json!({
+                    "scheduled_messages": [
+                        {"id": "Q1", "channel_id": "C1", "post_at": 1_900_000_000, "date_created": 1, "text": "standup reminder"},
+                        {"id": "Q2", "channel_id": "C2", "post_at": 1_900_000_100, "date_created": 1, "text": "lunch"},
+                    ],
+                    "response_metadata": {"next_cursor": "page2"},
+                })
+            } else {
+                json!({
+                    "scheduled_messages": [
+                        {"id": "Q3", "channel_id": "C1", "post_at": 1_900_000_200, "date_created": 1, "text": "retro reminder",
+                         "thread_ts": "1.000000", "blocks": [{"type": "divider"}]},
+                    ],
+                    "response_metadata": {"next_cursor": ""},
+                })
+            }
+        })
+        .on("/chat.scheduleMessage", |_| {
+            json!({"channel": "C1", "scheduled_message_id": "Q9", "post_at": "1900003800"})
+        })
+}
+
+#[test]
+fn list_follows_pages_and_filters() {
+    let client = slack();
+    let scheduled = ScheduledMessages::new(&client).page_size(2);
+    let all = scheduled.list(&ScheduledFilter::new()).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[2].thread_ts, Some(Ts::new(1, 0)));
+    assert_eq!(all[2].blocks.as_ref().map(Vec::len), Some(1));
+
+    let reminders = scheduled
+        .list(&ScheduledFilter::new().text("reminder"))
+        .unwrap();
+    let ids: Vec<&str> = reminders.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, ["Q1", "Q3"]);
+    assert_eq!(client.calls()[1].1["cursor"], "page2");
+    assert_eq!(client.calls()[0].1["limit"], 2);
+}
+
+#[test]
+fn cancel_deletes_matches() {
+    let client = slack();
+    let cancelled = ScheduledMessages::new(&client)
+        .cancel(&ScheduledFilter::new().latest(1_900_000_100))
+        .unwrap();
+    assert_eq!(cancelled.len(), 2);
+    let calls = client.calls();
+    let deletes: Vec<_> = calls
+        .iter()
+        .filter(|(path, _)| *path == "/chat.deleteScheduledMessage")
+        .map(|(_, body)| {
+            (
+                body["channel"].clone(),
+                body["scheduled_message_id"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        deletes,
+        [("C1".into(), "Q1".into()), ("C2".into(), "Q2".into())]
+    );
+}
+
+#[test]
+fn reschedule_recreates_before_deleting() {
+    let client = slack();
+    let scheduled = ScheduledMessages::new(&client);
+    let retro = scheduled
+        .list(&ScheduledFilter::new().text("retro"))
+        .unwrap()
+        .remove(0);
+    let post_at = PostAt::in_days(1);
+    let resp = scheduled.reschedule(&retro, post_at).unwrap();
+    assert_eq!(resp.scheduled_message_id, "Q9");
+
+    let calls = client.calls();
+    let tail: Vec<_> = calls[calls.len() - 2..].iter().map(|(p, _)| *p).collect();
+    assert_eq!(
+        tail,
+        ["/chat.scheduleMessage", "/chat.deleteScheduledMessage"]
+    );
+    let scheduled_body = &calls[calls.len() - 2].1;
+    assert_eq!(scheduled_body["post_at"], post_at.unix());
+    assert_eq!(scheduled_body["thread_ts"], "1.000000");
+    assert_eq!(scheduled_body["text"], "retro reminder");
+    assert_eq!(scheduled_body["blocks"][0]["type"], "divider");
+}
+
+#[test]
+fn reschedule_outside_the_window_sends_nothing() {
+    let client = slack();
+    let scheduled = ScheduledMessages::new(&client);
+    let retro = scheduled
+        .list(&ScheduledFilter::new().text("retro"))
+        .unwrap()
+        .remove(0);
+    let listed = client.calls().len();
+
+    let err = scheduled
+        .reschedule(&retro, PostAt::in_days(121))
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        RescheduleError::PostAt(PostAtError::TooFarAhead(_))
+    ));
+    assert_eq!(client.calls().len(), listed);
+}
+
+#[test]
+fn next_cursor_ignores_empty() {
+    let resp: ScheduledMessagesListResponse =
+        serde_json::from_str(r#"{"scheduled_messages":[],"response_metadata":{"next_cursor":""}}"#)
+            .unwrap();
+    assert_eq!(resp.next_cursor(), None);
+}
@@ -0,0 +1,92 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+
+mod common;
+
+use std::cell::Cell;
+
+use serde_json::json;
+use slaq::api::chat::post_message::PostMessage;
+use slaq::api::chat::split::SplitLimits;
+use slaq::blocks;
+use slaq::id::Ts;
+
+use common::MockSlack;
+
+/// Answers posts with increasing timestamps.
+fn slack() -> MockSlack {
+    let posted = Cell::new(0);
+    MockSlack::new().on("/chat.postMessage", move |_| {
+        posted.set(posted.get() + 1);
+        let ts = format!("{}.000000", posted.get());
+        json!({"channel": "C1", "ts": ts, "message": {"ts": ts}})
+    })
+}
+
+#[test]
+fn long_markdown_posts_as_a_thread() {
+    let log = (1..=1000)
+        .map(|i| format!("step {i:04} finished ok"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let markdown = format!("Build log:\n```\n{log}\n```");
+    let series = PostMessage::new("C1")
+        .markdown_text(markdown)
+        .username("ci")
+        .split();
+    assert!(!series.is_single());
+
+    let client = slack();
+    let handles = series.post(&client).unwrap();
+    let calls = client.bodies("/chat.postMessage");
+    assert_eq!(handles.len(), calls.len());
+    assert!(calls[0].get("thread_ts").is_none());
+    for call in &calls[1..] {
+        assert_eq!(call["thread_ts"], "1.000000");
+        assert_eq!(call["username"], "ci");
+    }
+    for call in &calls {
+        let text = call["markdown_text"].as_str().unwrap();
+        assert!(text.chars().count() <= 12_000);
+        assert_eq!(text.matches("```").count(), 2, "{text}");
+    }
+}
+
+#[test]
+fn block_lists_split_by_count_and_keep_text_on_first() {
+    let blocks: Vec<_> = (0..120)
+        .map(|i| {
+            blocks::Section::new()
+                .text(blocks::Text::mrkdwn(format!("row {i}")))
+                .build()
+        })
+        .collect();
+    let series = PostMessage::new("C1")
+        .text("Query results")
+        .blocks(blocks)
+        .thread_ts(Ts::new(9, 0))
+        .split_with(&SplitLimits::default());
+    let counts: Vec<usize> = series
+        .messages()
+        .map(|m| m.blocks.as_ref().map_or(0, Vec::len))
+        .collect();
+    assert_eq!(counts, [50, 50, 20]);
+    assert_eq!(series.first.text.as_deref(), Some("Query results"));
+    assert!(series.continuations.iter().all(|m| m.text.is_none()));
+
+    let client = slack();
+    series.post(&client).unwrap();
+    let calls = client.bodies("/chat.postMessage");
+    assert!(calls.iter().all(|c| c["thread_ts"] == "9.000000"));
+}
+
+#[test]
+fn fallback_text_of_split_blocks_is_bounded() {
+    let text = "line\n".repeat(2000);
+    let series = PostMessage::new("C1")
+        .text(text)
+        .blocks(vec![blocks::Divider::new().build()])
+        .split_with(&SplitLimits::default());
+    let first = series.first.text.unwrap();
+    assert!(first.chars().count() <= 4000);
+    assert!(first.starts_with("line\nline"));
+}
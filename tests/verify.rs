@@ -0,0 +1,47 @@
+#![allow(clippy::missing_errors_doc, clippy::must_use_candidate)]
+#![cfg(feature = "verify")]
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use slaq::verify::{SIGNATURE_HEADER, TIMESTAMP_HEADER, Verifier, VerifyError, sign};
+
+fn now() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        .to_string()
+}
+
+#[test]
+fn verifies_headers_from_a_request() {
+    let body = br#"{"type":"url_verification","challenge":"abc"}"#;
+    let timestamp = now();
+    let headers = HashMap::from([
+        (TIMESTAMP_HEADER, timestamp.clone()),
+        (SIGNATURE_HEADER, sign("old", &timestamp, body)),
+    ]);
+    let lookup = |name: &str| headers.get(name).map(String::as_str);
+
+    assert!(
+        Verifier::new("new")
+            .secret("old")
+            .verify_headers(lookup, body)
+            .is_ok()
+    );
+    assert_eq!(
+        Verifier::new("new").verify_headers(lookup, body),
+        Err(VerifyError::SignatureMismatch)
+    );
+}
+
+#[test]
+fn rejects_stale_requests() {
+    let body = b"payload=%7B%7D";
+    let signature = sign("secret", "1531420618", body);
+    assert_eq!(
+        Verifier::new("secret").verify("1531420618", &signature, body),
+        Err(VerifyError::Expired(1_531_420_618))
+    );
+}